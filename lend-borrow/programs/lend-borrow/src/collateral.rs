@@ -0,0 +1,153 @@
+//! Bundle collateral: loans backed by more than one NFT of the collection.
+//!
+//! The first NFT of every loan still travels through the named accounts of
+//! `borrow`, `repay` and `liquidate`. Every additional NFT is passed through
+//! `remaining_accounts` together with its metadata, which has to show it as a
+//! verified member of the pool's collection. `ActiveLoan` records all mints and
+//! vault token accounts so that they can be checked when the loan is settled.
+
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::MetadataAccount;
+use anchor_spl::token::{self, TokenAccount, Transfer};
+
+use crate::errors::ErrorCodes;
+use crate::states::ActiveLoan;
+
+/// Reads an SPL token account that was passed through `remaining_accounts`.
+pub fn token_account(info: &AccountInfo) -> Result<TokenAccount> {
+    if info.owner != &token::ID {
+        return Err(ErrorCode::AccountOwnedByWrongProgram.into());
+    }
+
+    TokenAccount::try_deserialize(&mut &info.try_borrow_data()?[..])
+}
+
+/// Fails unless `metadata` is the metadata of `mint` and shows it as a verified
+/// member of `collection`.
+pub fn require_collection_member(
+    metadata: &AccountInfo,
+    mint: &Pubkey,
+    collection: &Pubkey,
+) -> Result<()> {
+    // only the metadata program can write accounts it owns, so their mint can be trusted
+    if metadata.owner != &MetadataAccount::owner() {
+        return Err(ErrorCode::AccountOwnedByWrongProgram.into());
+    }
+
+    let metadata = MetadataAccount::try_deserialize(&mut &metadata.try_borrow_data()?[..])?;
+
+    let verified = matches!(
+        &metadata.collection,
+        Some(member) if member.verified && member.key == *collection
+    );
+
+    if !verified || metadata.mint != *mint {
+        return Err(ErrorCodes::NotCollectionMember.into());
+    }
+
+    Ok(())
+}
+
+/// Moves the additional NFTs of a bundle from the borrower into the vault.
+///
+/// `remaining_accounts` holds one `[borrower token account, mint, vault token account,
+/// metadata]` group per additional NFT, the vault token accounts have to be created
+/// beforehand with the vault authority as owner. Returns the mints and vault token
+/// accounts of the additional NFTs in the order they were passed.
+pub fn deposit_bundle<'info>(
+    remaining_accounts: &[AccountInfo<'info>],
+    first_mint: Pubkey,
+    collection: Pubkey,
+    borrower: &AccountInfo<'info>,
+    vault_authority: Pubkey,
+    token_program: &AccountInfo<'info>,
+) -> Result<(Vec<Pubkey>, Vec<Pubkey>)> {
+    let mut mints = vec![];
+    let mut vaults = vec![];
+
+    for accounts in remaining_accounts.chunks(4) {
+        let [borrower_asset_account, asset_mint, vault_asset_account, metadata] = accounts else {
+            return Err(ErrorCodes::CollateralMismatch.into());
+        };
+
+        require_collection_member(metadata, &asset_mint.key(), &collection)?;
+
+        let borrower_asset = token_account(borrower_asset_account)?;
+        let vault_asset = token_account(vault_asset_account)?;
+
+        if borrower_asset.owner != borrower.key()
+            || borrower_asset.mint != asset_mint.key()
+            || vault_asset.owner != vault_authority
+            || vault_asset.mint != asset_mint.key()
+            || asset_mint.key() == first_mint
+            || mints.contains(&asset_mint.key())
+        {
+            return Err(ErrorCodes::CollateralMismatch.into());
+        }
+
+        let cpi_accounts = Transfer {
+            from: borrower_asset_account.clone(),
+            to: vault_asset_account.clone(),
+            authority: borrower.clone(),
+        };
+
+        token::transfer(CpiContext::new(token_program.clone(), cpi_accounts), 1)?;
+
+        mints.push(asset_mint.key());
+        vaults.push(vault_asset_account.key());
+    }
+
+    Ok((mints, vaults))
+}
+
+/// Moves the additional NFTs of a bundle out of the vault to `recipient`.
+///
+/// `remaining_accounts` holds one `[vault token account, recipient token account]`
+/// pair per additional NFT, in the order they were recorded on the loan.
+pub fn release_bundle<'info>(
+    active_loan: &ActiveLoan,
+    remaining_accounts: &[AccountInfo<'info>],
+    recipient: Pubkey,
+    vault_authority: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    signer: &[&[&[u8]]],
+) -> Result<()> {
+    let extra_mints = active_loan.collateral_mints.iter().skip(1);
+    let extra_vaults = active_loan.collateral_vaults.iter().skip(1);
+
+    if remaining_accounts.len() != 2 * extra_mints.len() {
+        return Err(ErrorCodes::CollateralMismatch.into());
+    }
+
+    for ((accounts, mint), vault) in remaining_accounts
+        .chunks(2)
+        .zip(extra_mints)
+        .zip(extra_vaults)
+    {
+        let [vault_asset_account, recipient_asset_account] = accounts else {
+            return Err(ErrorCodes::CollateralMismatch.into());
+        };
+
+        let recipient_asset = token_account(recipient_asset_account)?;
+
+        if vault_asset_account.key() != *vault
+            || recipient_asset.owner != recipient
+            || recipient_asset.mint != *mint
+        {
+            return Err(ErrorCodes::CollateralMismatch.into());
+        }
+
+        let cpi_accounts = Transfer {
+            from: vault_asset_account.clone(),
+            to: recipient_asset_account.clone(),
+            authority: vault_authority.clone(),
+        };
+
+        token::transfer(
+            CpiContext::new_with_signer(token_program.clone(), cpi_accounts, signer),
+            1,
+        )?;
+    }
+
+    Ok(())
+}
@@ -8,4 +8,32 @@ pub enum ErrorCodes {
     LoanAlreadyRepaid,
     #[msg("Cannot Liquidate Loan Yet")]
     CannotLiquidateYet,
+    #[msg("Offer Expiry Must Be In The Future")]
+    InvalidOfferExpiry,
+    #[msg("Offer Expired")]
+    OfferExpired,
+    #[msg("Offer Not Expired Yet")]
+    OfferNotExpired,
+    #[msg("Math Overflow")]
+    MathOverflow,
+    #[msg("Loan Duration Must Be Positive")]
+    InvalidDuration,
+    #[msg("Account Already Migrated")]
+    AlreadyMigrated,
+    #[msg("Invalid Collateral Count")]
+    InvalidCollateralCount,
+    #[msg("Collateral Accounts Do Not Match The Loan")]
+    CollateralMismatch,
+    #[msg("Loan Already Liquidated")]
+    LoanAlreadyLiquidated,
+    #[msg("Signer Is Not A Party To The Loan")]
+    NotLoanParty,
+    #[msg("Repayment Timestamp Must Be In The Future")]
+    InvalidRepayTimestamp,
+    #[msg("Relend Cap Must Be Positive")]
+    InvalidRelendCap,
+    #[msg("Terms Were Proposed For A Previous Loan")]
+    StaleTermsProposal,
+    #[msg("Collateral Is Not A Verified Member Of The Collection")]
+    NotCollectionMember,
 }
@@ -0,0 +1,113 @@
+pub use anchor_lang::prelude::*;
+
+use anchor_lang::system_program;
+
+use crate::errors::ErrorCodes;
+use crate::states::{ActiveLoan, CollectionPool, Offer, TermsProposal};
+
+/// The counterparty of a proposal accepts the new terms, which are applied to
+/// the loan together with the settlement of any change of principal.
+#[derive(Accounts)]
+pub struct AcceptTerms<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"terms-proposal",
+            active_loan.key().as_ref(),
+            active_loan.loan_ts.to_le_bytes().as_ref()
+        ],
+        bump = terms_proposal.bump,
+        has_one = proposer,
+        constraint = terms_proposal.borrower == active_loan.borrower @ ErrorCodes::StaleTermsProposal,
+        close = proposer
+    )]
+    pub terms_proposal: Box<Account<'info, TermsProposal>>,
+
+    #[account(
+        mut,
+        seeds = [b"active-loan", offer.key().as_ref()],
+        bump = active_loan.bump,
+        constraint = acceptor.key() != proposer.key() @ ErrorCodes::NotLoanParty,
+        constraint = acceptor.key() == active_loan.lender
+            || acceptor.key() == active_loan.borrower @ ErrorCodes::NotLoanParty
+    )]
+    pub active_loan: Box<Account<'info, ActiveLoan>>,
+
+    #[account(mut)]
+    pub offer: Box<Account<'info, Offer>>,
+
+    #[account(
+        mut,
+        constraint = collection_pool.key() == active_loan.collection
+    )]
+    pub collection_pool: Box<Account<'info, CollectionPool>>,
+
+    /// CHECK: Checked against the proposer stored on the proposal
+    #[account(mut)]
+    pub proposer: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub acceptor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub clock: Sysvar<'info, Clock>,
+}
+
+impl<'info> AcceptTerms<'info> {
+    fn transfer_to_proposer_context(
+        &self,
+    ) -> CpiContext<'_, '_, '_, 'info, system_program::Transfer<'info>> {
+        let cpi_accounts = system_program::Transfer {
+            from: self.acceptor.to_account_info().clone(),
+            to: self.proposer.clone(),
+        };
+
+        CpiContext::new(self.system_program.to_account_info(), cpi_accounts)
+    }
+}
+
+pub fn handler(ctx: Context<AcceptTerms>) -> Result<()> {
+    let proposal = &ctx.accounts.terms_proposal;
+
+    ctx.accounts.active_loan.require_outstanding()?;
+
+    if proposal.repay_ts <= ctx.accounts.clock.unix_timestamp {
+        return Err(ErrorCodes::InvalidRepayTimestamp.into());
+    }
+
+    let principal_delta = proposal
+        .offer_lamport_amount
+        .abs_diff(ctx.accounts.offer.offer_lamport_amount);
+
+    if proposal.escrowed_lamports > 0 {
+        // the proposer owed the difference and locked it when proposing
+        let proposal_info = ctx.accounts.terms_proposal.to_account_info();
+        let mut proposal_lamports = proposal_info.try_borrow_mut_lamports()?;
+        **proposal_lamports = proposal_lamports
+            .checked_sub(proposal.escrowed_lamports)
+            .ok_or(ErrorCodes::MathOverflow)?;
+
+        let mut acceptor_lamports = ctx.accounts.acceptor.try_borrow_mut_lamports()?;
+        **acceptor_lamports = acceptor_lamports
+            .checked_add(proposal.escrowed_lamports)
+            .ok_or(ErrorCodes::MathOverflow)?;
+    } else if principal_delta > 0 {
+        // otherwise the acceptor owes the difference
+        system_program::transfer(ctx.accounts.transfer_to_proposer_context(), principal_delta)?;
+    }
+
+    ctx.accounts.collection_pool.record_principal_change(
+        ctx.accounts.offer.offer_lamport_amount,
+        proposal.offer_lamport_amount,
+    )?;
+
+    let offer = &mut ctx.accounts.offer;
+
+    offer.offer_lamport_amount = proposal.offer_lamport_amount;
+    offer.repay_lamport_amount = proposal.repay_lamport_amount;
+
+    ctx.accounts.active_loan.repay_ts = proposal.repay_ts;
+
+    Ok(())
+}
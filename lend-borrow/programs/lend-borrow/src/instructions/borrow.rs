@@ -4,7 +4,9 @@ use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
 
 use crate::states::{ActiveLoan, CollectionPool, Offer, Vault};
 
+use crate::collateral;
 use crate::errors::ErrorCodes;
+use crate::lifecycle;
 
 #[derive(Accounts)]
 pub struct Borrow<'info> {
@@ -37,11 +39,18 @@ pub struct Borrow<'info> {
     )]
     pub vault_asset_account: Account<'info, TokenAccount>,
 
-    /// CHECK: This is not dangerous
-    #[account(mut)]
+    /// CHECK: PDA of the collection pool that owns every vault token account
+    #[account(
+        mut,
+        seeds = [collection_pool.key().as_ref()],
+        bump
+    )]
     pub vault_authority: AccountInfo<'info>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = collection_pool.key() == offer_loan.collection
+    )]
     pub collection_pool: Box<Account<'info, CollectionPool>>,
 
     #[account(mut)]
@@ -76,46 +85,70 @@ impl<'info> Borrow<'info> {
     }
 }
 
-pub fn handler(ctx: Context<Borrow>, minimum_balance_for_rent_exemption: u64) -> Result<()> {
+/// Bundle loans pass one `[borrower token account, mint, vault token account, metadata]`
+/// group per NFT after the first through `remaining_accounts`.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, Borrow<'info>>,
+    minimum_balance_for_rent_exemption: u64,
+) -> Result<()> {
     let active_loan = &mut ctx.accounts.active_loan;
     let offer = &mut ctx.accounts.offer_loan;
     let collection = &mut ctx.accounts.collection_pool;
 
-    if offer.is_loan_taken == true {
-        return Err(ErrorCodes::LoanAlreadyTaken.into());
+    lifecycle::borrow(
+        active_loan,
+        offer,
+        collection,
+        ctx.accounts.borrower.key(),
+        ctx.accounts.clock.unix_timestamp,
+    )?;
+
+    if ctx.remaining_accounts.len() != 4 * (offer.required_collateral() - 1) {
+        return Err(ErrorCodes::CollateralMismatch.into());
     }
 
     active_loan.collection = collection.key();
     active_loan.offer_account = offer.key();
-    active_loan.lender = offer.lender.key();
-    active_loan.borrower = ctx.accounts.borrower.key();
     active_loan.mint = ctx.accounts.asset_mint.key();
-    active_loan.loan_ts = ctx.accounts.clock.unix_timestamp;
-    active_loan.repay_ts = ctx.accounts.clock.unix_timestamp + collection.duration;
-    active_loan.is_repaid = false;
-    active_loan.is_liquidated = false;
     active_loan.bump = ctx.bumps.active_loan;
-
-    offer.borrower = ctx.accounts.borrower.key();
-    offer.is_loan_taken = true;
+    active_loan.version = ActiveLoan::VERSION;
 
     // here that this transfer is invoked from the anchor_spl whereas the transfer we were using before was system_program::transfer()
     // his is because when we have to transfer lamports, we would need to use system program, but if we need to transfer SPL Tokens,
     // which include tokens and NFTs, then we need to use the token program.
     token::transfer(ctx.accounts.transfer_to_vault_context(), 1)?;
 
+    let (bundle_mints, bundle_vaults) = collateral::deposit_bundle(
+        ctx.remaining_accounts,
+        ctx.accounts.asset_mint.key(),
+        ctx.accounts.collection_pool.collection_id,
+        &ctx.accounts.borrower.to_account_info(),
+        ctx.accounts.vault_authority.key(),
+        &ctx.accounts.token_program.to_account_info(),
+    )?;
+
+    let active_loan = &mut ctx.accounts.active_loan;
+
+    active_loan.collateral_mints = [vec![active_loan.mint], bundle_mints].concat();
+    active_loan.collateral_vaults =
+        [vec![ctx.accounts.vault_asset_account.key()], bundle_vaults].concat();
+
     let vault_lamports_initial: u64 = ctx.accounts.vault_account.to_account_info().lamports();
 
     let transfer_amount = vault_lamports_initial
         .checked_sub(minimum_balance_for_rent_exemption)
-        .unwrap();
-
-    **ctx
-        .accounts
-        .vault_account
-        .to_account_info()
-        .try_borrow_mut_lamports()? -= transfer_amount;
-    **ctx.accounts.borrower.try_borrow_mut_lamports()? += transfer_amount;
+        .ok_or(ErrorCodes::MathOverflow)?;
+
+    let vault_info = ctx.accounts.vault_account.to_account_info();
+    let mut vault_lamports = vault_info.try_borrow_mut_lamports()?;
+    **vault_lamports = vault_lamports
+        .checked_sub(transfer_amount)
+        .ok_or(ErrorCodes::MathOverflow)?;
+
+    let mut borrower_lamports = ctx.accounts.borrower.try_borrow_mut_lamports()?;
+    **borrower_lamports = borrower_lamports
+        .checked_add(transfer_amount)
+        .ok_or(ErrorCodes::MathOverflow)?;
 
     Ok(())
 }
@@ -0,0 +1,21 @@
+pub use anchor_lang::prelude::*;
+
+use crate::states::TermsProposal;
+
+/// The proposer withdraws a proposal, getting back the rent and any escrowed lamports.
+#[derive(Accounts)]
+pub struct CancelTerms<'info> {
+    #[account(
+        mut,
+        has_one = proposer,
+        close = proposer
+    )]
+    pub terms_proposal: Box<Account<'info, TermsProposal>>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+}
+
+pub fn handler(_ctx: Context<CancelTerms>) -> Result<()> {
+    Ok(())
+}
@@ -0,0 +1,51 @@
+pub use anchor_lang::prelude::*;
+
+use crate::states::{CollectionPool, Offer, Vault};
+
+use crate::errors::ErrorCodes;
+use crate::lifecycle;
+
+/// Anyone can clean up an offer once it has expired, closing both the offer
+/// and its vault so that the offered lamports and the rent go back to the lender.
+#[derive(Accounts)]
+pub struct CleanupExpiredOffer<'info> {
+    #[account(
+        mut,
+        has_one = lender,
+        close = lender,
+    )]
+    pub offer_loan: Box<Account<'info, Offer>>,
+
+    #[account(
+        mut,
+        constraint = vault_account.offer == offer_loan.key(),
+        close = lender
+    )]
+    pub vault_account: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        constraint = collection_pool.key() == offer_loan.collection
+    )]
+    pub collection_pool: Box<Account<'info, CollectionPool>>,
+
+    /// CHECK: Checked against the lender stored on the offer
+    #[account(mut)]
+    pub lender: AccountInfo<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+}
+
+#[access_control(offer_expired(&ctx.accounts.offer_loan, &ctx.accounts.clock))]
+pub fn handler(ctx: Context<CleanupExpiredOffer>) -> Result<()> {
+    lifecycle::close_offer(&ctx.accounts.offer_loan, &mut ctx.accounts.collection_pool)
+}
+
+// Access Control Modifier
+fn offer_expired<'info>(offer: &Account<'info, Offer>, clock: &Sysvar<'info, Clock>) -> Result<()> {
+    if !offer.is_expired(clock.unix_timestamp) {
+        return Err(ErrorCodes::OfferNotExpired.into());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+pub use anchor_lang::prelude::*;
+
+use crate::lifecycle;
+use crate::states::Offer;
+
+/// The lender opts an offer in or out of auto-relending. While enabled,
+/// repaying a loan deposits principal plus interest back into the vault,
+/// compounding up to `relend_cap`, and reopens the offer for a new borrower.
+#[derive(Accounts)]
+pub struct ConfigureAutoRelend<'info> {
+    #[account(
+        mut,
+        has_one = lender,
+    )]
+    pub offer_loan: Box<Account<'info, Offer>>,
+
+    pub lender: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<ConfigureAutoRelend>,
+    auto_relend: bool,
+    relend_cap: u64,
+) -> Result<()> {
+    lifecycle::configure_auto_relend(&mut ctx.accounts.offer_loan, auto_relend, relend_cap)
+}
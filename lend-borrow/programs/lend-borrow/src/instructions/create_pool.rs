@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::ErrorCodes;
 use crate::states::CollectionPool;
 
 /// init: initialise the collection pool account
@@ -29,6 +30,10 @@ pub struct CreatePool<'info> {
 }
 
 pub fn handler(ctx: Context<CreatePool>, collection_id: Pubkey, duration: i64) -> Result<()> {
+    if duration <= 0 {
+        return Err(ErrorCodes::InvalidDuration.into());
+    }
+
     let collection = &mut ctx.accounts.collection_pool;
 
     collection.collection_id = collection_id;
@@ -36,6 +41,7 @@ pub fn handler(ctx: Context<CreatePool>, collection_id: Pubkey, duration: i64) -
     collection.duration = duration;
     collection.total_offers = 0;
     collection.bump = ctx.bumps.collection_pool;
+    collection.version = CollectionPool::VERSION;
 
     Ok(())
 }
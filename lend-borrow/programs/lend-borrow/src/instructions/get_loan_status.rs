@@ -0,0 +1,72 @@
+pub use anchor_lang::prelude::*;
+
+use crate::math;
+use crate::states::{ActiveLoan, Offer};
+
+/// Read-only view of a loan, meant to be simulated rather than sent.
+/// The returned `LoanStatus` is written with `set_return_data` so that
+/// clients don't have to re-implement the due amount and liquidation rules.
+#[derive(Accounts)]
+pub struct GetLoanStatus<'info> {
+    #[account(
+        seeds=[b"active-loan", offer.key().as_ref()],
+        bump=active_loan.bump
+    )]
+    pub active_loan: Box<Account<'info, ActiveLoan>>,
+
+    pub offer: Box<Account<'info, Offer>>,
+
+    pub clock: Sysvar<'info, Clock>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct LoanStatus {
+    /// Lamports the borrower received
+    pub principal: u64,
+
+    /// Lamports still owed to the lender, zero once repaid or liquidated
+    pub amount_due: u64,
+
+    /// Repayment Timestamp
+    pub repay_ts: i64,
+
+    /// Seconds until the lender can liquidate, zero once they can
+    pub seconds_until_liquidatable: i64,
+
+    /// Lender can liquidate the loan right now
+    pub is_liquidatable: bool,
+
+    /// Repaid
+    pub is_repaid: bool,
+
+    /// Liquidated
+    pub is_liquidated: bool,
+}
+
+pub fn handler(ctx: Context<GetLoanStatus>) -> Result<LoanStatus> {
+    let active_loan = &ctx.accounts.active_loan;
+    let offer = &ctx.accounts.offer;
+    let now = ctx.accounts.clock.unix_timestamp;
+
+    let amount_due = if active_loan.is_outstanding() {
+        offer.repay_lamport_amount
+    } else {
+        0
+    };
+
+    let seconds_until_liquidatable = if active_loan.is_outstanding() {
+        math::seconds_until_liquidatable(active_loan.repay_ts, now)
+    } else {
+        0
+    };
+
+    Ok(LoanStatus {
+        principal: offer.offer_lamport_amount,
+        amount_due,
+        repay_ts: active_loan.repay_ts,
+        seconds_until_liquidatable,
+        is_liquidatable: active_loan.is_liquidatable(now),
+        is_repaid: active_loan.is_repaid,
+        is_liquidated: active_loan.is_liquidated,
+    })
+}
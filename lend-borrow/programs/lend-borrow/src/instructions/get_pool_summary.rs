@@ -0,0 +1,85 @@
+pub use anchor_lang::prelude::*;
+
+use crate::math;
+use crate::states::CollectionPool;
+
+/// Read-only view of a collection pool, meant to be simulated rather than sent.
+/// The returned `PoolSummary` is written with `set_return_data`.
+#[derive(Accounts)]
+pub struct GetPoolSummary<'info> {
+    pub collection_pool: Box<Account<'info, CollectionPool>>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "sim", derive(serde::Serialize))]
+pub struct PoolSummary {
+    /// NFT Collection ID
+    pub collection_id: Pubkey,
+
+    /// Pool Owner
+    pub pool_owner: Pubkey,
+
+    /// Loan Duration
+    pub duration: i64,
+
+    /// Total Loans
+    pub total_offers: u64,
+
+    /// Lamports in offers still open for borrowing
+    pub offered_liquidity: u64,
+
+    /// Principal of loans neither repaid nor liquidated
+    pub outstanding_principal: u64,
+
+    /// Number of loans neither repaid nor liquidated
+    pub active_loans: u64,
+
+    /// Number of loans ever taken from the pool
+    pub total_borrows: u64,
+
+    /// Interest paid to lenders over the lifetime of the pool
+    pub cumulative_interest_paid: u64,
+
+    /// Number of liquidated loans
+    pub default_count: u64,
+
+    /// Principal of liquidated loans
+    pub defaulted_principal: u64,
+
+    /// Outstanding principal as a share of outstanding principal plus open offers, in basis points
+    pub utilisation_bps: u64,
+
+    /// Liquidated loans as a share of all loans taken, in basis points
+    pub default_rate_bps: u64,
+}
+
+impl From<&CollectionPool> for PoolSummary {
+    fn from(collection: &CollectionPool) -> Self {
+        PoolSummary {
+            collection_id: collection.collection_id,
+            pool_owner: collection.pool_owner,
+            duration: collection.duration,
+            total_offers: collection.total_offers,
+            offered_liquidity: collection.offered_liquidity,
+            outstanding_principal: collection.outstanding_principal,
+            active_loans: collection.active_loans,
+            total_borrows: collection.total_borrows,
+            cumulative_interest_paid: collection.cumulative_interest_paid,
+            default_count: collection.default_count,
+            defaulted_principal: collection.defaulted_principal,
+            utilisation_bps: math::share_bps(
+                collection.outstanding_principal,
+                collection
+                    .outstanding_principal
+                    .saturating_add(collection.offered_liquidity),
+            ),
+            default_rate_bps: math::share_bps(collection.default_count, collection.total_borrows),
+        }
+    }
+}
+
+pub fn handler(ctx: Context<GetPoolSummary>) -> Result<PoolSummary> {
+    let collection: &CollectionPool = &ctx.accounts.collection_pool;
+
+    Ok(PoolSummary::from(collection))
+}
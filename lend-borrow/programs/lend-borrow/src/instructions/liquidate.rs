@@ -1,7 +1,9 @@
 pub use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
 
+use crate::collateral;
 use crate::errors::ErrorCodes;
+use crate::lifecycle;
 pub use crate::states::{ActiveLoan, CollectionPool, Offer};
 
 #[derive(Accounts)]
@@ -16,14 +18,22 @@ pub struct Liquidate<'info> {
     #[account(mut)]
     pub offer: Box<Account<'info, Offer>>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = collection_pool.key() == active_loan.collection
+    )]
     pub collection_pool: Box<Account<'info, CollectionPool>>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = asset_mint.key() == active_loan.mint @ ErrorCodes::CollateralMismatch
+    )]
     pub asset_mint: Account<'info, Mint>,
 
     #[account(
         mut,
+        constraint = vault_asset_account.key() == active_loan.primary_vault(&crate::ID)
+            @ ErrorCodes::CollateralMismatch,
         constraint = vault_asset_account.mint == asset_mint.key(),
         constraint = vault_asset_account.owner == vault_authority.key()
     )]
@@ -36,7 +46,10 @@ pub struct Liquidate<'info> {
     )]
     pub lender_asset_account: Account<'info, TokenAccount>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = lender.key() == active_loan.lender @ ErrorCodes::NotLoanParty
+    )]
     pub lender: Signer<'info>,
 
     /// CHECK: This is not dangerous
@@ -47,17 +60,18 @@ pub struct Liquidate<'info> {
     pub clock: Sysvar<'info, Clock>,
 }
 
-// Access Controls are functions which you would want to run before executing the transaction
-#[access_control(repayment_time_over(&ctx.accounts.active_loan, &ctx.accounts.clock))]
-pub fn handler(ctx: Context<Liquidate>) -> Result<()> {
+/// Bundle loans pass one `[vault token account, lender token account]`
+/// pair per NFT after the first through `remaining_accounts`.
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, Liquidate<'info>>) -> Result<()> {
     let active_loan = &mut ctx.accounts.active_loan;
     let collection = &mut ctx.accounts.collection_pool;
 
-    if active_loan.is_repaid {
-        return Err(ErrorCodes::LoanAlreadyRepaid.into());
-    }
-
-    active_loan.is_liquidated = true;
+    lifecycle::liquidate(
+        active_loan,
+        &ctx.accounts.offer,
+        collection,
+        ctx.accounts.clock.unix_timestamp,
+    )?;
 
     let (_vault_authority, vault_auth_bump) =
         Pubkey::find_program_address(&[collection.key().as_ref()], ctx.program_id);
@@ -82,17 +96,14 @@ pub fn handler(ctx: Context<Liquidate>) -> Result<()> {
 
     token::transfer(cpi_ctx, 1)?;
 
-    Ok(())
-}
-
-// Access Control Modifier
-fn repayment_time_over<'info>(
-    active_loan: &Account<'info, ActiveLoan>,
-    clock: &Sysvar<'info, Clock>,
-) -> Result<()> {
-    if !(active_loan.repay_ts < clock.unix_timestamp) {
-        return Err(ErrorCodes::CannotLiquidateYet.into());
-    }
+    collateral::release_bundle(
+        active_loan,
+        ctx.remaining_accounts,
+        ctx.accounts.lender.key(),
+        &ctx.accounts.vault_authority,
+        &ctx.accounts.token_program.to_account_info(),
+        signer,
+    )?;
 
     Ok(())
 }
@@ -0,0 +1,80 @@
+pub use anchor_lang::prelude::*;
+
+use anchor_lang::system_program;
+
+use crate::errors::ErrorCodes;
+use crate::states::Versioned;
+
+/// Upgrades an account created by an older version of the program in place.
+/// Accounts written before versioning was introduced are too short for the
+/// current layout, so the account is grown first (the payer tops up the rent)
+/// and only then read back and stamped with the current version.
+#[derive(Accounts)]
+pub struct Migrate<'info> {
+    /// CHECK: Older layouts can't be deserialised, so the owner is checked here
+    /// and the discriminator is checked in the handler.
+    #[account(mut, owner = crate::ID)]
+    pub account: UncheckedAccount<'info>,
+
+    /// account paying for the additional rent of the grown account.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Migrate<'info> {
+    fn transfer_rent_context(
+        &self,
+    ) -> CpiContext<'_, '_, '_, 'info, system_program::Transfer<'info>> {
+        let cpi_accounts = system_program::Transfer {
+            from: self.payer.to_account_info().clone(),
+            to: self.account.to_account_info().clone(),
+        };
+
+        CpiContext::new(self.system_program.to_account_info(), cpi_accounts)
+    }
+}
+
+pub fn handler<T: Versioned>(ctx: Context<Migrate>) -> Result<()> {
+    let account_info = ctx.accounts.account.to_account_info();
+
+    {
+        let data = account_info.try_borrow_data()?;
+
+        if data.len() < 8 || data[..8] != T::DISCRIMINATOR {
+            return Err(ErrorCode::AccountDiscriminatorMismatch.into());
+        }
+    }
+
+    if account_info.data_len() < T::SPACE {
+        let rent_exempt_lamports = Rent::get()?.minimum_balance(T::SPACE);
+        let top_up = rent_exempt_lamports.saturating_sub(account_info.lamports());
+
+        if top_up > 0 {
+            system_program::transfer(ctx.accounts.transfer_rent_context(), top_up)?;
+        }
+
+        account_info.realloc(T::SPACE, true)?;
+    }
+
+    let mut data = account_info.try_borrow_mut_data()?;
+
+    upgrade::<T>(&mut data)
+}
+
+/// Upgrades account `data` already grown to `T::SPACE` and stamps it with the
+/// current version.
+pub fn upgrade<T: Versioned>(data: &mut [u8]) -> Result<()> {
+    let mut account = T::try_deserialize(&mut &data[..])?;
+
+    if account.version() >= T::VERSION {
+        return Err(ErrorCodes::AlreadyMigrated.into());
+    }
+
+    account.upgrade_unversioned();
+    account.set_version(T::VERSION);
+    account.try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
@@ -1,13 +1,31 @@
+pub mod accept_terms;
 pub mod borrow;
+pub mod cancel_terms;
+pub mod cleanup_expired_offer;
+pub mod configure_auto_relend;
 pub mod create_pool;
+pub mod get_loan_status;
+pub mod get_pool_summary;
 pub mod liquidate;
+pub mod migrate;
 pub mod offer_loan;
+pub mod propose_terms;
 pub mod repay;
+pub mod repay_on_behalf;
 pub mod withdraw_offer;
 
+pub use accept_terms::*;
 pub use borrow::*;
+pub use cancel_terms::*;
+pub use cleanup_expired_offer::*;
+pub use configure_auto_relend::*;
 pub use create_pool::*;
+pub use get_loan_status::*;
+pub use get_pool_summary::*;
 pub use liquidate::*;
+pub use migrate::*;
 pub use offer_loan::*;
+pub use propose_terms::*;
 pub use repay::*;
+pub use repay_on_behalf::*;
 pub use withdraw_offer::*;
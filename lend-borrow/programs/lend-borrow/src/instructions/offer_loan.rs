@@ -2,7 +2,10 @@ pub use anchor_lang::prelude::*;
 
 use anchor_lang::system_program;
 
-pub use crate::states::{CollectionPool, Offer, Vault};
+pub use crate::states::{ActiveLoan, CollectionPool, Offer, Vault};
+
+use crate::errors::ErrorCodes;
+use crate::lifecycle;
 
 #[derive(Accounts)]
 pub struct OfferLoan<'info> {
@@ -46,6 +49,8 @@ pub struct OfferLoan<'info> {
 
     /// account to transfer lamports from the lender to the vault.
     pub system_program: Program<'info, System>,
+
+    pub clock: Sysvar<'info, Clock>,
 }
 
 impl<'info> OfferLoan<'info> {
@@ -61,21 +66,37 @@ impl<'info> OfferLoan<'info> {
     }
 }
 
-pub fn handler(ctx: Context<OfferLoan>, offer_amount: u64) -> Result<()> {
+pub fn handler(
+    ctx: Context<OfferLoan>,
+    offer_amount: u64,
+    expires_at: i64,
+    collateral_count: u8,
+) -> Result<()> {
+    if collateral_count == 0 || collateral_count as usize > ActiveLoan::MAX_COLLATERAL {
+        return Err(ErrorCodes::InvalidCollateralCount.into());
+    }
+
     let offer_account = &mut ctx.accounts.offer_loan;
     let collection = &mut ctx.accounts.collection_pool;
     let vault = &mut ctx.accounts.vault_account;
 
+    lifecycle::open_offer(
+        offer_account,
+        collection,
+        ctx.accounts.lender.key(),
+        offer_amount,
+        expires_at,
+        ctx.accounts.clock.unix_timestamp,
+    )?;
+
     offer_account.collection = collection.key();
-    offer_account.offer_lamport_amount = offer_amount;
-    offer_account.repay_lamport_amount = offer_amount + offer_amount * 10 / 100;
-    offer_account.lender = ctx.accounts.lender.key();
+    offer_account.collateral_count = collateral_count;
     offer_account.bump = ctx.bumps.offer_loan;
-
-    collection.total_offers += 1;
+    offer_account.version = Offer::VERSION;
 
     vault.offer = offer_account.key();
     vault.bump = ctx.bumps.vault_account;
+    vault.version = Vault::VERSION;
 
     system_program::transfer(ctx.accounts.transfer_to_vault_context(), offer_amount)?;
 
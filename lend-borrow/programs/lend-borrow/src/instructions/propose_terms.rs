@@ -0,0 +1,109 @@
+pub use anchor_lang::prelude::*;
+
+use anchor_lang::system_program;
+
+use crate::errors::ErrorCodes;
+use crate::math;
+use crate::states::{ActiveLoan, Offer, TermsProposal};
+
+/// Either party of an active loan can propose new terms, which take effect
+/// once the counterparty accepts them with `accept_terms`.
+///
+/// When the proposer is the one who owes lamports for a change of principal
+/// (the lender raising it or the borrower lowering it), the difference is
+/// locked in the proposal so that acceptance can settle it atomically.
+///
+/// Proposals are keyed by the loan's start as well, since a relending offer
+/// recreates its loan at the same address and a proposal left open on the
+/// previous loan must neither block nor apply to the next one.
+#[derive(Accounts)]
+pub struct ProposeTerms<'info> {
+    #[account(
+        init,
+        seeds = [
+            b"terms-proposal",
+            active_loan.key().as_ref(),
+            active_loan.loan_ts.to_le_bytes().as_ref()
+        ],
+        bump,
+        payer = proposer,
+        space = TermsProposal::LEN
+    )]
+    pub terms_proposal: Box<Account<'info, TermsProposal>>,
+
+    #[account(
+        seeds = [b"active-loan", offer.key().as_ref()],
+        bump = active_loan.bump,
+        constraint = proposer.key() == active_loan.lender
+            || proposer.key() == active_loan.borrower @ ErrorCodes::NotLoanParty
+    )]
+    pub active_loan: Box<Account<'info, ActiveLoan>>,
+
+    pub offer: Box<Account<'info, Offer>>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub clock: Sysvar<'info, Clock>,
+}
+
+impl<'info> ProposeTerms<'info> {
+    fn transfer_to_proposal_context(
+        &self,
+    ) -> CpiContext<'_, '_, '_, 'info, system_program::Transfer<'info>> {
+        let cpi_accounts = system_program::Transfer {
+            from: self.proposer.to_account_info().clone(),
+            to: self.terms_proposal.to_account_info().clone(),
+        };
+
+        CpiContext::new(self.system_program.to_account_info(), cpi_accounts)
+    }
+}
+
+pub fn handler(
+    ctx: Context<ProposeTerms>,
+    offer_amount: u64,
+    interest_bps: u64,
+    repay_ts: i64,
+) -> Result<()> {
+    let active_loan = &ctx.accounts.active_loan;
+    let offer = &ctx.accounts.offer;
+
+    active_loan.require_outstanding()?;
+
+    if repay_ts <= ctx.accounts.clock.unix_timestamp {
+        return Err(ErrorCodes::InvalidRepayTimestamp.into());
+    }
+
+    let proposer_is_lender = ctx.accounts.proposer.key() == active_loan.lender;
+
+    let escrowed_lamports = if proposer_is_lender {
+        offer_amount.saturating_sub(offer.offer_lamport_amount)
+    } else {
+        offer.offer_lamport_amount.saturating_sub(offer_amount)
+    };
+
+    let proposal = &mut ctx.accounts.terms_proposal;
+
+    proposal.active_loan = active_loan.key();
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.offer_lamport_amount = offer_amount;
+    proposal.repay_lamport_amount = math::repay_amount_at_rate(offer_amount, interest_bps)?;
+    proposal.repay_ts = repay_ts;
+    proposal.escrowed_lamports = escrowed_lamports;
+    proposal.loan_ts = active_loan.loan_ts;
+    proposal.borrower = active_loan.borrower;
+    proposal.bump = ctx.bumps.terms_proposal;
+    proposal.version = TermsProposal::VERSION;
+
+    if escrowed_lamports > 0 {
+        system_program::transfer(
+            ctx.accounts.transfer_to_proposal_context(),
+            escrowed_lamports,
+        )?;
+    }
+
+    Ok(())
+}
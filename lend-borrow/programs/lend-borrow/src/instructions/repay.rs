@@ -1,18 +1,28 @@
 pub use anchor_lang::prelude::*;
 use anchor_lang::system_program;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer};
 
+use crate::collateral;
+use crate::errors::ErrorCodes;
+use crate::lifecycle;
 pub use crate::states::{ActiveLoan, CollectionPool, Offer, Vault};
 
 #[derive(Accounts)]
 pub struct Repay<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [b"active-loan", offer.key().as_ref()],
+        bump = active_loan.bump
+    )]
     pub active_loan: Box<Account<'info, ActiveLoan>>,
 
     #[account(mut)]
     pub offer: Box<Account<'info, Offer>>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = collection_pool.key() == active_loan.collection
+    )]
     pub collection_pool: Box<Account<'info, CollectionPool>>,
 
     /// CHECK: This is not dangerous
@@ -22,7 +32,10 @@ pub struct Repay<'info> {
     )]
     pub lender: AccountInfo<'info>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = asset_mint.key() == active_loan.mint @ ErrorCodes::CollateralMismatch
+    )]
     pub asset_mint: Account<'info, Mint>,
 
     #[account(
@@ -34,18 +47,26 @@ pub struct Repay<'info> {
 
     #[account(
         mut,
+        constraint = vault_asset_account.key() == active_loan.primary_vault(&crate::ID)
+            @ ErrorCodes::CollateralMismatch,
         constraint = vault_asset_account.mint == asset_mint.key(),
         constraint = vault_asset_account.owner == vault_authority.key()
     )]
     pub vault_asset_account: Account<'info, TokenAccount>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = vault_account.offer == offer.key()
+    )]
     pub vault_account: Account<'info, Vault>,
 
     /// CHECK: This is not dangerous
     pub vault_authority: AccountInfo<'info>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = borrower.key() == active_loan.borrower
+    )]
     pub borrower: Signer<'info>,
 
     pub token_program: Program<'info, Token>,
@@ -54,27 +75,70 @@ pub struct Repay<'info> {
 }
 
 impl<'info> Repay<'info> {
-    fn transfer_to_lender_context(
+    fn settlement<'a>(
         &self,
-    ) -> CpiContext<'_, '_, '_, 'info, system_program::Transfer<'info>> {
-        let cpi_accounts = system_program::Transfer {
-            from: self.borrower.to_account_info().clone(),
-            to: self.lender.clone(),
-        };
-
-        CpiContext::new(self.system_program.to_account_info().clone(), cpi_accounts)
+        remaining_accounts: &'a [AccountInfo<'info>],
+    ) -> Settlement<'a, 'info> {
+        Settlement {
+            payer: self.borrower.to_account_info(),
+            lender: self.lender.clone(),
+            borrower: self.borrower.to_account_info(),
+            borrower_asset_account: self.borrower_asset_account.to_account_info(),
+            vault_asset_account: self.vault_asset_account.to_account_info(),
+            vault_account: self.vault_account.to_account_info(),
+            vault_authority: self.vault_authority.clone(),
+            token_program: self.token_program.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            remaining_accounts,
+        }
     }
 }
 
-pub fn handler(ctx: Context<Repay>) -> Result<()> {
-    let active_loan = &mut ctx.accounts.active_loan;
-    let collection = &mut ctx.accounts.collection_pool;
-    let offer = &mut ctx.accounts.offer;
+/// Accounts taking part in settling a loan, shared by `repay` and `repay_on_behalf`
+/// which only differ in who pays the lender.
+pub struct Settlement<'a, 'info> {
+    /// account paying the repay amount to the lender
+    pub payer: AccountInfo<'info>,
+
+    pub lender: AccountInfo<'info>,
+
+    /// borrower recorded on the loan, who gets the collateral back
+    pub borrower: AccountInfo<'info>,
+
+    pub borrower_asset_account: AccountInfo<'info>,
+
+    pub vault_asset_account: AccountInfo<'info>,
 
-    active_loan.is_repaid = true;
+    /// vault of the offer, refilled when the offer auto-relends
+    pub vault_account: AccountInfo<'info>,
+
+    pub vault_authority: AccountInfo<'info>,
+
+    pub token_program: AccountInfo<'info>,
+
+    pub system_program: AccountInfo<'info>,
+
+    /// one `[vault token account, borrower token account]` pair per bundled NFT after the first
+    pub remaining_accounts: &'a [AccountInfo<'info>],
+}
+
+/// Marks the loan repaid, returns every NFT backing it to the borrower and pays the lender.
+///
+/// When the offer auto-relends, the repaid lamports go back into the vault instead
+/// (anything above the lender's cap is paid out) and the offer reopens. The loan
+/// and its vault token account are closed so that the next `borrow` can recreate
+/// them, handing their rent back to the borrower who paid it.
+pub fn settle<'info>(
+    active_loan: &mut Account<'info, ActiveLoan>,
+    offer: &mut Offer,
+    collection: &mut Account<'info, CollectionPool>,
+    settlement: Settlement<'_, 'info>,
+    program_id: &Pubkey,
+) -> Result<()> {
+    let repayment = lifecycle::repay(active_loan, offer, collection)?;
 
     let (_vault_authority, vault_auth_bump) =
-        Pubkey::find_program_address(&[collection.key().as_ref()], ctx.program_id);
+        Pubkey::find_program_address(&[collection.key().as_ref()], program_id);
 
     let col_seeds = collection.key();
 
@@ -82,27 +146,81 @@ pub fn handler(ctx: Context<Repay>) -> Result<()> {
 
     let signer = &[&authority_seeds[..]];
 
-    let repay_amount = offer.repay_lamport_amount;
-
     let cpi_accounts = Transfer {
-        from: ctx.accounts.vault_asset_account.to_account_info().clone(),
-        to: ctx
-            .accounts
-            .borrower_asset_account
-            .to_account_info()
-            .clone(),
-        authority: ctx.accounts.vault_authority.clone(),
+        from: settlement.vault_asset_account.clone(),
+        to: settlement.borrower_asset_account.clone(),
+        authority: settlement.vault_authority.clone(),
     };
 
-    let cpi_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info().clone(),
+    let cpi_ctx =
+        CpiContext::new_with_signer(settlement.token_program.clone(), cpi_accounts, signer);
+
+    token::transfer(cpi_ctx, 1)?;
+
+    collateral::release_bundle(
+        active_loan,
+        settlement.remaining_accounts,
+        settlement.borrower.key(),
+        &settlement.vault_authority,
+        &settlement.token_program,
+        signer,
+    )?;
+
+    if !offer.auto_relend {
+        return pay(&settlement, &settlement.lender, repayment.payout);
+    }
+
+    pay(
+        &settlement,
+        &settlement.vault_account,
+        repayment.relend_amount,
+    )?;
+
+    if repayment.payout > 0 {
+        pay(&settlement, &settlement.lender, repayment.payout)?;
+    }
+
+    let cpi_accounts = CloseAccount {
+        account: settlement.vault_asset_account.clone(),
+        destination: settlement.borrower.clone(),
+        authority: settlement.vault_authority.clone(),
+    };
+
+    token::close_account(CpiContext::new_with_signer(
+        settlement.token_program.clone(),
         cpi_accounts,
         signer,
-    );
+    ))?;
 
-    token::transfer(cpi_ctx, 1)?;
+    active_loan.close(settlement.borrower.clone())
+}
 
-    system_program::transfer(ctx.accounts.transfer_to_lender_context(), repay_amount)?;
+fn pay<'info>(
+    settlement: &Settlement<'_, 'info>,
+    to: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let cpi_accounts = system_program::Transfer {
+        from: settlement.payer.clone(),
+        to: to.clone(),
+    };
+
+    system_program::transfer(
+        CpiContext::new(settlement.system_program.clone(), cpi_accounts),
+        amount,
+    )
+}
 
-    Ok(())
+/// Bundle loans pass one `[vault token account, borrower token account]`
+/// pair per NFT after the first through `remaining_accounts`.
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, Repay<'info>>) -> Result<()> {
+    let settlement = ctx.accounts.settlement(ctx.remaining_accounts);
+
+    settle(
+        &mut ctx.accounts.active_loan,
+        &mut ctx.accounts.offer,
+        &mut ctx.accounts.collection_pool,
+        settlement,
+        ctx.program_id,
+    )
 }
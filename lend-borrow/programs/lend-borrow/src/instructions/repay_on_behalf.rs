@@ -0,0 +1,113 @@
+pub use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use super::repay::{settle, Settlement};
+use crate::errors::ErrorCodes;
+pub use crate::states::{ActiveLoan, CollectionPool, Offer, Vault};
+
+/// Any payer (a friend, a DAO or a bot) can settle a loan before it gets liquidated,
+/// the NFT still goes back to the borrower recorded on the loan.
+#[derive(Accounts)]
+pub struct RepayOnBehalf<'info> {
+    #[account(
+        mut,
+        seeds=[b"active-loan", offer.key().as_ref()],
+        bump=active_loan.bump
+    )]
+    pub active_loan: Box<Account<'info, ActiveLoan>>,
+
+    #[account(mut)]
+    pub offer: Box<Account<'info, Offer>>,
+
+    #[account(
+        mut,
+        constraint = collection_pool.key() == active_loan.collection
+    )]
+    pub collection_pool: Box<Account<'info, CollectionPool>>,
+
+    /// CHECK: This is not dangerous
+    #[account(
+        mut,
+        constraint = lender.key() == offer.lender.key()
+    )]
+    pub lender: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = asset_mint.key() == active_loan.mint @ ErrorCodes::CollateralMismatch
+    )]
+    pub asset_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = borrower_asset_account.mint == asset_mint.key(),
+        constraint = borrower_asset_account.owner == active_loan.borrower
+    )]
+    pub borrower_asset_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = vault_asset_account.key() == active_loan.primary_vault(&crate::ID)
+            @ ErrorCodes::CollateralMismatch,
+        constraint = vault_asset_account.mint == asset_mint.key(),
+        constraint = vault_asset_account.owner == vault_authority.key()
+    )]
+    pub vault_asset_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = vault_account.offer == offer.key()
+    )]
+    pub vault_account: Account<'info, Vault>,
+
+    /// CHECK: This is not dangerous
+    pub vault_authority: AccountInfo<'info>,
+
+    /// CHECK: Checked against the borrower stored on the loan
+    #[account(
+        mut,
+        constraint = borrower.key() == active_loan.borrower
+    )]
+    pub borrower: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RepayOnBehalf<'info> {
+    fn settlement<'a>(
+        &self,
+        remaining_accounts: &'a [AccountInfo<'info>],
+    ) -> Settlement<'a, 'info> {
+        Settlement {
+            payer: self.payer.to_account_info(),
+            lender: self.lender.clone(),
+            borrower: self.borrower.clone(),
+            borrower_asset_account: self.borrower_asset_account.to_account_info(),
+            vault_asset_account: self.vault_asset_account.to_account_info(),
+            vault_account: self.vault_account.to_account_info(),
+            vault_authority: self.vault_authority.clone(),
+            token_program: self.token_program.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            remaining_accounts,
+        }
+    }
+}
+
+/// Bundle loans pass one `[vault token account, borrower token account]`
+/// pair per NFT after the first through `remaining_accounts`.
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, RepayOnBehalf<'info>>) -> Result<()> {
+    let settlement = ctx.accounts.settlement(ctx.remaining_accounts);
+
+    settle(
+        &mut ctx.accounts.active_loan,
+        &mut ctx.accounts.offer,
+        &mut ctx.accounts.collection_pool,
+        settlement,
+        ctx.program_id,
+    )
+}
@@ -3,6 +3,7 @@ pub use anchor_lang::prelude::*;
 use crate::states::{CollectionPool, Offer, Vault};
 
 use crate::errors::ErrorCodes;
+use crate::lifecycle;
 
 #[derive(Accounts)]
 pub struct WithdrawOffer<'info> {
@@ -18,7 +19,10 @@ pub struct WithdrawOffer<'info> {
     )]
     pub vault_account: Account<'info, Vault>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = collection_pool.key() == offer_loan.collection
+    )]
     pub collection_pool: Box<Account<'info, CollectionPool>>,
 
     #[account(mut)]
@@ -28,28 +32,24 @@ pub struct WithdrawOffer<'info> {
 }
 
 pub fn handler(ctx: Context<WithdrawOffer>, minimum_balance_for_rent_exemption: u64) -> Result<()> {
-    let collection = &mut ctx.accounts.collection_pool;
-
-    if ctx.accounts.offer_loan.is_loan_taken == true {
-        return Err(ErrorCodes::LoanAlreadyTaken.into());
-    }
-
-    collection.total_offers -= 1;
+    lifecycle::close_offer(&ctx.accounts.offer_loan, &mut ctx.accounts.collection_pool)?;
 
     let vault_lamports_initial: u64 = ctx.accounts.vault_account.to_account_info().lamports();
 
     let transfer_amount = vault_lamports_initial
         .checked_sub(minimum_balance_for_rent_exemption)
-        .unwrap();
+        .ok_or(ErrorCodes::MathOverflow)?;
 
-    **ctx
-        .accounts
-        .vault_account
-        .to_account_info()
-        .try_borrow_mut_lamports()? -= transfer_amount;
+    let vault_info = ctx.accounts.vault_account.to_account_info();
+    let mut vault_lamports = vault_info.try_borrow_mut_lamports()?;
+    **vault_lamports = vault_lamports
+        .checked_sub(transfer_amount)
+        .ok_or(ErrorCodes::MathOverflow)?;
 
     let mut lamports_ref = ctx.accounts.lender.try_borrow_mut_lamports()?;
-    **lamports_ref += transfer_amount;
+    **lamports_ref = lamports_ref
+        .checked_add(transfer_amount)
+        .ok_or(ErrorCodes::MathOverflow)?;
 
     Ok(())
 }
@@ -1,5 +1,10 @@
+pub mod collateral;
 pub mod errors;
 pub mod instructions;
+pub mod lifecycle;
+pub mod math;
+#[cfg(feature = "sim")]
+pub mod sim;
 pub mod states;
 
 pub use errors::ErrorCodes;
@@ -20,8 +25,13 @@ pub mod lend_borrow {
         instructions::create_pool::handler(ctx, collection_id, duration)
     }
 
-    pub fn offer_loan(ctx: Context<OfferLoan>, offer_amount: u64) -> Result<()> {
-        instructions::offer_loan::handler(ctx, offer_amount)
+    pub fn offer_loan(
+        ctx: Context<OfferLoan>,
+        offer_amount: u64,
+        expires_at: i64,
+        collateral_count: u8,
+    ) -> Result<()> {
+        instructions::offer_loan::handler(ctx, offer_amount, expires_at, collateral_count)
     }
 
     pub fn withdraw_offer(
@@ -31,17 +41,83 @@ pub mod lend_borrow {
         instructions::withdraw_offer::handler(ctx, minimum_balance_for_rent_exemption)
     }
 
-    pub fn borrow(ctx: Context<Borrow>, minimum_balance_for_rent_exemption: u64) -> Result<()> {
+    pub fn cleanup_expired_offer(ctx: Context<CleanupExpiredOffer>) -> Result<()> {
+        instructions::cleanup_expired_offer::handler(ctx)
+    }
+
+    pub fn configure_auto_relend(
+        ctx: Context<ConfigureAutoRelend>,
+        auto_relend: bool,
+        relend_cap: u64,
+    ) -> Result<()> {
+        instructions::configure_auto_relend::handler(ctx, auto_relend, relend_cap)
+    }
+
+    pub fn borrow<'info>(
+        ctx: Context<'_, '_, '_, 'info, Borrow<'info>>,
+        minimum_balance_for_rent_exemption: u64,
+    ) -> Result<()> {
         instructions::borrow::handler(ctx, minimum_balance_for_rent_exemption)
     }
 
-    pub fn repay(ctx: Context<Repay>) -> Result<()> {
+    pub fn repay<'info>(ctx: Context<'_, '_, '_, 'info, Repay<'info>>) -> Result<()> {
         instructions::repay::handler(ctx)
     }
 
-    pub fn liquidate(ctx: Context<Liquidate>) -> Result<()> {
+    pub fn repay_on_behalf<'info>(
+        ctx: Context<'_, '_, '_, 'info, RepayOnBehalf<'info>>,
+    ) -> Result<()> {
+        instructions::repay_on_behalf::handler(ctx)
+    }
+
+    pub fn liquidate<'info>(ctx: Context<'_, '_, '_, 'info, Liquidate<'info>>) -> Result<()> {
         instructions::liquidate::handler(ctx)
     }
+
+    pub fn propose_terms(
+        ctx: Context<ProposeTerms>,
+        offer_amount: u64,
+        interest_bps: u64,
+        repay_ts: i64,
+    ) -> Result<()> {
+        instructions::propose_terms::handler(ctx, offer_amount, interest_bps, repay_ts)
+    }
+
+    pub fn accept_terms(ctx: Context<AcceptTerms>) -> Result<()> {
+        instructions::accept_terms::handler(ctx)
+    }
+
+    pub fn cancel_terms(ctx: Context<CancelTerms>) -> Result<()> {
+        instructions::cancel_terms::handler(ctx)
+    }
+
+    pub fn get_loan_status(ctx: Context<GetLoanStatus>) -> Result<LoanStatus> {
+        instructions::get_loan_status::handler(ctx)
+    }
+
+    pub fn get_pool_summary(ctx: Context<GetPoolSummary>) -> Result<PoolSummary> {
+        instructions::get_pool_summary::handler(ctx)
+    }
+
+    pub fn migrate_collection_pool(ctx: Context<Migrate>) -> Result<()> {
+        instructions::migrate::handler::<CollectionPool>(ctx)
+    }
+
+    pub fn migrate_offer(ctx: Context<Migrate>) -> Result<()> {
+        instructions::migrate::handler::<Offer>(ctx)
+    }
+
+    pub fn migrate_vault(ctx: Context<Migrate>) -> Result<()> {
+        instructions::migrate::handler::<Vault>(ctx)
+    }
+
+    pub fn migrate_active_loan(ctx: Context<Migrate>) -> Result<()> {
+        instructions::migrate::handler::<ActiveLoan>(ctx)
+    }
+
+    pub fn migrate_terms_proposal(ctx: Context<Migrate>) -> Result<()> {
+        instructions::migrate::handler::<TermsProposal>(ctx)
+    }
 }
 
 #[derive(Accounts)]
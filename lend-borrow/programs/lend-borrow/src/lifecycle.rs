@@ -0,0 +1,158 @@
+//! State transitions of an offer and the loan taken against it.
+//!
+//! Each function checks and applies the account changes of one instruction,
+//! leaving lamport and token transfers to the handler. The handlers and the
+//! offline simulator both go through them, so the two can't disagree on
+//! when an action is allowed or what it does to the pool.
+
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCodes;
+use crate::math;
+use crate::states::{ActiveLoan, CollectionPool, Offer};
+
+/// Where the lamports repaid on a loan go
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Repayment {
+    /// Offered again from the offer's vault, zero unless the offer auto-relends
+    pub relend_amount: u64,
+
+    /// Paid out to the lender
+    pub payout: u64,
+}
+
+/// `lender` offers `amount` lamports until `expires_at`
+pub fn open_offer(
+    offer: &mut Offer,
+    collection: &mut CollectionPool,
+    lender: Pubkey,
+    amount: u64,
+    expires_at: i64,
+    now: i64,
+) -> Result<()> {
+    if expires_at <= now {
+        return Err(ErrorCodes::InvalidOfferExpiry.into());
+    }
+
+    offer.offer_lamport_amount = amount;
+    offer.repay_lamport_amount = math::repay_amount(amount)?;
+    offer.lender = lender;
+    offer.expires_at = expires_at;
+
+    collection.total_offers = collection
+        .total_offers
+        .checked_add(1)
+        .ok_or(ErrorCodes::MathOverflow)?;
+    collection.record_offer(amount)
+}
+
+/// Opts the offer in or out of relending repaid lamports up to `relend_cap`
+pub fn configure_auto_relend(offer: &mut Offer, auto_relend: bool, relend_cap: u64) -> Result<()> {
+    if auto_relend && relend_cap == 0 {
+        return Err(ErrorCodes::InvalidRelendCap.into());
+    }
+
+    offer.auto_relend = auto_relend;
+    offer.relend_cap = relend_cap;
+
+    Ok(())
+}
+
+/// The lender takes back an offer nobody borrowed
+pub fn close_offer(offer: &Offer, collection: &mut CollectionPool) -> Result<()> {
+    if offer.is_loan_taken {
+        return Err(ErrorCodes::LoanAlreadyTaken.into());
+    }
+
+    collection.total_offers = collection
+        .total_offers
+        .checked_sub(1)
+        .ok_or(ErrorCodes::MathOverflow)?;
+    collection.record_offer_closed(offer.offer_lamport_amount);
+
+    Ok(())
+}
+
+/// `borrower` takes the loan offered by `offer` at `now`
+pub fn borrow(
+    active_loan: &mut ActiveLoan,
+    offer: &mut Offer,
+    collection: &mut CollectionPool,
+    borrower: Pubkey,
+    now: i64,
+) -> Result<()> {
+    if offer.is_loan_taken {
+        return Err(ErrorCodes::LoanAlreadyTaken.into());
+    }
+
+    if offer.is_expired(now) {
+        return Err(ErrorCodes::OfferExpired.into());
+    }
+
+    active_loan.lender = offer.lender;
+    active_loan.borrower = borrower;
+    active_loan.loan_ts = now;
+    active_loan.repay_ts = math::repay_timestamp(now, collection.duration)?;
+    active_loan.is_repaid = false;
+    active_loan.is_liquidated = false;
+
+    offer.borrower = borrower;
+    offer.is_loan_taken = true;
+
+    collection.record_borrow(offer.offer_lamport_amount)
+}
+
+/// The loan is paid back. An auto-relending offer reopens with the repaid
+/// lamports up to its cap, anything above it goes to the lender.
+pub fn repay(
+    active_loan: &mut ActiveLoan,
+    offer: &mut Offer,
+    collection: &mut CollectionPool,
+) -> Result<Repayment> {
+    active_loan.require_outstanding()?;
+
+    active_loan.is_repaid = true;
+
+    let repay_amount = offer.repay_lamport_amount;
+
+    collection.record_repayment(offer.offer_lamport_amount, repay_amount)?;
+
+    if !offer.auto_relend {
+        return Ok(Repayment {
+            relend_amount: 0,
+            payout: repay_amount,
+        });
+    }
+
+    let (relend_amount, payout) = math::relend_split(repay_amount, offer.relend_cap);
+
+    offer.is_loan_taken = false;
+    offer.borrower = Pubkey::default();
+    offer.offer_lamport_amount = relend_amount;
+    offer.repay_lamport_amount = math::repay_amount(relend_amount)?;
+
+    collection.record_offer(relend_amount)?;
+
+    Ok(Repayment {
+        relend_amount,
+        payout,
+    })
+}
+
+/// The lender claims the collateral of a loan that wasn't repaid in time
+pub fn liquidate(
+    active_loan: &mut ActiveLoan,
+    offer: &Offer,
+    collection: &mut CollectionPool,
+    now: i64,
+) -> Result<()> {
+    if !math::is_liquidatable(active_loan.repay_ts, now) {
+        return Err(ErrorCodes::CannotLiquidateYet.into());
+    }
+
+    active_loan.require_outstanding()?;
+
+    active_loan.is_liquidated = true;
+
+    collection.record_default(offer.offer_lamport_amount)
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCodes;
+
+/// Interest charged on every loan, as a percentage of the offered amount
+pub const INTEREST_PERCENT: u64 = 10;
+
+/// Amount the borrower has to pay back for a loan of `offer_amount` lamports,
+/// i.e. the offered amount plus interest.
+pub fn repay_amount(offer_amount: u64) -> Result<u64> {
+    let interest = offer_amount
+        .checked_mul(INTEREST_PERCENT)
+        .ok_or(ErrorCodes::MathOverflow)?
+        .checked_div(100)
+        .ok_or(ErrorCodes::MathOverflow)?;
+
+    offer_amount
+        .checked_add(interest)
+        .ok_or_else(|| ErrorCodes::MathOverflow.into())
+}
+
+/// Timestamp after which a loan taken at `loan_ts` can be liquidated.
+pub fn repay_timestamp(loan_ts: i64, duration: i64) -> Result<i64> {
+    loan_ts
+        .checked_add(duration)
+        .ok_or_else(|| ErrorCodes::MathOverflow.into())
+}
+
+/// A loan can be liquidated once its repayment timestamp has passed.
+pub fn is_liquidatable(repay_ts: i64, now: i64) -> bool {
+    repay_ts < now
+}
+
+/// Seconds left until a loan becomes liquidatable, zero once it already is.
+pub fn seconds_until_liquidatable(repay_ts: i64, now: i64) -> i64 {
+    repay_ts.saturating_sub(now).saturating_add(1).max(0)
+}
+
+/// Denominator of interest rates expressed in basis points
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Amount the borrower has to pay back for a loan of `offer_amount` lamports
+/// at an interest rate of `interest_bps` basis points.
+pub fn repay_amount_at_rate(offer_amount: u64, interest_bps: u64) -> Result<u64> {
+    let interest = (offer_amount as u128)
+        .checked_mul(interest_bps as u128)
+        .ok_or(ErrorCodes::MathOverflow)?
+        / BPS_DENOMINATOR as u128;
+
+    let interest = u64::try_from(interest).map_err(|_| ErrorCodes::MathOverflow)?;
+
+    offer_amount
+        .checked_add(interest)
+        .ok_or_else(|| ErrorCodes::MathOverflow.into())
+}
+
+/// `part` as a share of `total` in basis points, zero when `total` is zero.
+pub fn share_bps(part: u64, total: u64) -> u64 {
+    if total == 0 {
+        return 0;
+    }
+
+    (part as u128 * BPS_DENOMINATOR as u128 / total as u128) as u64
+}
+
+/// Splits the lamports repaid on an auto-relending offer into the amount that is
+/// offered again, at most `relend_cap`, and the excess paid out to the lender.
+pub fn relend_split(repay_amount: u64, relend_cap: u64) -> (u64, u64) {
+    let relend_amount = repay_amount.min(relend_cap);
+
+    (relend_amount, repay_amount - relend_amount)
+}
@@ -0,0 +1,469 @@
+//! Deterministic loan-schedule simulator.
+//!
+//! Replays a scenario of offers, borrows, clock advances, repayments and
+//! liquidations against a single collection pool without a Solana runtime.
+//! Every step goes through the same `crate::lifecycle` transitions the
+//! handlers run, on the same `Offer`, `ActiveLoan` and `CollectionPool`
+//! accounts, so offline models can't drift from the program.
+//!
+//! Scenarios are written either as JSON (see `Scenario`) or as CSV with one
+//! step per line, the first line setting up the pool:
+//!
+//! ```text
+//! # comments and blank lines are ignored
+//! pool,<duration>[,<start timestamp>]
+//! offer,<lender>,<amount>,<expires at>[,<relend cap>]
+//! withdraw,<offer>
+//! cleanup,<offer>
+//! borrow,<offer>,<borrower>
+//! advance,<seconds>
+//! repay,<offer>
+//! liquidate,<offer>
+//! ```
+//!
+//! Offers are referred to by their position among the `offer` steps, starting
+//! at zero. Balances are net lamport flows per participant, rent is ignored.
+//!
+//! The simulator is only built with the `sim` feature, which keeps serde out
+//! of the on-chain program.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use anchor_lang::prelude::{AnchorDeserialize, Error, Pubkey};
+use anchor_lang::solana_program::hash::hash;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::ErrorCodes;
+use crate::instructions::PoolSummary;
+use crate::lifecycle;
+use crate::states::{ActiveLoan, CollectionPool, Offer};
+
+/// Pool configuration plus the steps to replay against it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Scenario {
+    /// Loan duration of the pool, in seconds
+    pub duration: i64,
+
+    /// Clock at the start of the scenario
+    #[serde(default)]
+    pub start_ts: i64,
+
+    pub steps: Vec<Step>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum Step {
+    /// `offer_loan`, auto-relending up to `relend_cap` when one is given
+    Offer {
+        lender: String,
+        amount: u64,
+        expires_at: i64,
+        #[serde(default)]
+        relend_cap: Option<u64>,
+    },
+
+    /// `withdraw_offer`
+    Withdraw { offer: usize },
+
+    /// `cleanup_expired_offer`
+    Cleanup { offer: usize },
+
+    /// `borrow`
+    Borrow { offer: usize, borrower: String },
+
+    /// Moves the clock forward
+    Advance { seconds: i64 },
+
+    /// `repay`
+    Repay { offer: usize },
+
+    /// `liquidate`
+    Liquidate { offer: usize },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SimError {
+    /// The scenario could not be read
+    Parse { line: usize, message: String },
+
+    /// A step was rejected the same way the program would reject it
+    Rejected { step: usize, reason: String },
+}
+
+impl fmt::Display for SimError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SimError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+            SimError::Rejected { step, reason } => write!(f, "step {}: {}", step, reason),
+        }
+    }
+}
+
+impl std::error::Error for SimError {}
+
+/// A loan the lender liquidated
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct Liquidation {
+    pub offer: usize,
+    pub lender: String,
+    pub borrower: String,
+    pub principal: u64,
+    pub liquidated_at: i64,
+}
+
+/// State of the pool once every step has been replayed
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct Report {
+    /// Clock after the last step
+    pub now: i64,
+
+    /// Net lamports each participant gained (positive) or spent (negative)
+    pub balances: BTreeMap<String, i128>,
+
+    /// Lamports sitting in the vault of each offer
+    pub vaults: Vec<u64>,
+
+    pub liquidations: Vec<Liquidation>,
+
+    /// Same aggregates `get_pool_summary` returns on chain
+    pub pool: PoolSummary,
+}
+
+impl Scenario {
+    pub fn from_json(json: &str) -> Result<Scenario, SimError> {
+        serde_json::from_str(json).map_err(|err| SimError::Parse {
+            line: err.line(),
+            message: err.to_string(),
+        })
+    }
+
+    pub fn from_csv(csv: &str) -> Result<Scenario, SimError> {
+        let mut scenario: Option<Scenario> = None;
+
+        for (index, raw) in csv.lines().enumerate() {
+            let line = index + 1;
+            let text = raw.trim();
+
+            if text.is_empty() || text.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = text.split(',').map(str::trim).collect();
+            let parse_error = |message: String| SimError::Parse { line, message };
+
+            let Some(scenario) = scenario.as_mut() else {
+                if fields[0] != "pool" || !(2..=3).contains(&fields.len()) {
+                    return Err(parse_error(
+                        "scenario has to start with pool,<duration>[,<start timestamp>]".into(),
+                    ));
+                }
+
+                scenario = Some(Scenario {
+                    duration: parse_field(&fields, 1, line)?,
+                    start_ts: match fields.get(2) {
+                        Some(_) => parse_field(&fields, 2, line)?,
+                        None => 0,
+                    },
+                    steps: vec![],
+                });
+
+                continue;
+            };
+
+            let expected_fields = match fields[0] {
+                "offer" => 4..=5,
+                "borrow" => 3..=3,
+                "withdraw" | "cleanup" | "advance" | "repay" | "liquidate" => 2..=2,
+                action => return Err(parse_error(format!("unknown action `{}`", action))),
+            };
+
+            if !expected_fields.contains(&fields.len()) {
+                return Err(parse_error(format!(
+                    "`{}` takes {} to {} fields",
+                    fields[0],
+                    expected_fields.start(),
+                    expected_fields.end()
+                )));
+            }
+
+            scenario.steps.push(match fields[0] {
+                "offer" => Step::Offer {
+                    lender: fields[1].to_string(),
+                    amount: parse_field(&fields, 2, line)?,
+                    expires_at: parse_field(&fields, 3, line)?,
+                    relend_cap: match fields.get(4) {
+                        Some(_) => Some(parse_field(&fields, 4, line)?),
+                        None => None,
+                    },
+                },
+                "withdraw" => Step::Withdraw {
+                    offer: parse_field(&fields, 1, line)?,
+                },
+                "cleanup" => Step::Cleanup {
+                    offer: parse_field(&fields, 1, line)?,
+                },
+                "borrow" => Step::Borrow {
+                    offer: parse_field(&fields, 1, line)?,
+                    borrower: fields[2].to_string(),
+                },
+                "advance" => Step::Advance {
+                    seconds: parse_field(&fields, 1, line)?,
+                },
+                "repay" => Step::Repay {
+                    offer: parse_field(&fields, 1, line)?,
+                },
+                _ => Step::Liquidate {
+                    offer: parse_field(&fields, 1, line)?,
+                },
+            });
+        }
+
+        scenario.ok_or(SimError::Parse {
+            line: 0,
+            message: "scenario is empty".into(),
+        })
+    }
+}
+
+impl Report {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("report always serialises")
+    }
+}
+
+fn parse_field<T: std::str::FromStr>(
+    fields: &[&str],
+    index: usize,
+    line: usize,
+) -> Result<T, SimError>
+where
+    T::Err: fmt::Display,
+{
+    fields[index].parse().map_err(|err| SimError::Parse {
+        line,
+        message: format!("field {} `{}`: {}", index + 1, fields[index], err),
+    })
+}
+
+struct SimOffer {
+    lender: String,
+    account: Offer,
+    vault: u64,
+    is_closed: bool,
+    loan: Option<SimLoan>,
+}
+
+struct SimLoan {
+    borrower: String,
+    account: ActiveLoan,
+}
+
+/// Replays every step of `scenario`, stopping at the first one the program would reject.
+pub fn run(scenario: &Scenario) -> Result<Report, SimError> {
+    if scenario.duration <= 0 {
+        return Err(rejected(0, ErrorCodes::InvalidDuration));
+    }
+
+    let mut pool: CollectionPool = zeroed(CollectionPool::LEN);
+    pool.duration = scenario.duration;
+    pool.version = CollectionPool::VERSION;
+
+    let mut now = scenario.start_ts;
+    let mut offers: Vec<SimOffer> = vec![];
+    let mut balances: BTreeMap<String, i128> = BTreeMap::new();
+    let mut liquidations = vec![];
+
+    for (step_index, step) in scenario.steps.iter().enumerate() {
+        let reject = |error: ErrorCodes| rejected(step_index, error);
+        let program_error = |error: Error| rejected_by_program(step_index, error);
+
+        match step {
+            Step::Offer {
+                lender,
+                amount,
+                expires_at,
+                relend_cap,
+            } => {
+                let mut account: Offer = zeroed(Offer::LEN);
+
+                lifecycle::open_offer(
+                    &mut account,
+                    &mut pool,
+                    participant(lender),
+                    *amount,
+                    *expires_at,
+                    now,
+                )
+                .map_err(program_error)?;
+
+                if let Some(relend_cap) = relend_cap {
+                    lifecycle::configure_auto_relend(&mut account, true, *relend_cap)
+                        .map_err(program_error)?;
+                }
+
+                offers.push(SimOffer {
+                    lender: lender.clone(),
+                    account,
+                    vault: *amount,
+                    is_closed: false,
+                    loan: None,
+                });
+
+                credit(&mut balances, lender, -(*amount as i128));
+            }
+
+            Step::Withdraw { offer } | Step::Cleanup { offer } => {
+                let offer_state = open_offer(&mut offers, *offer, step_index)?;
+
+                if matches!(step, Step::Cleanup { .. }) && !offer_state.account.is_expired(now) {
+                    return Err(reject(ErrorCodes::OfferNotExpired));
+                }
+
+                lifecycle::close_offer(&offer_state.account, &mut pool).map_err(program_error)?;
+
+                offer_state.is_closed = true;
+                credit(
+                    &mut balances,
+                    &offer_state.lender,
+                    offer_state.vault as i128,
+                );
+                offer_state.vault = 0;
+            }
+
+            Step::Borrow { offer, borrower } => {
+                let offer_state = open_offer(&mut offers, *offer, step_index)?;
+                let mut account: ActiveLoan = zeroed(ActiveLoan::LEN);
+
+                lifecycle::borrow(
+                    &mut account,
+                    &mut offer_state.account,
+                    &mut pool,
+                    participant(borrower),
+                    now,
+                )
+                .map_err(program_error)?;
+
+                offer_state.loan = Some(SimLoan {
+                    borrower: borrower.clone(),
+                    account,
+                });
+
+                credit(&mut balances, borrower, offer_state.vault as i128);
+                offer_state.vault = 0;
+            }
+
+            Step::Advance { seconds } => {
+                if *seconds < 0 {
+                    return Err(SimError::Rejected {
+                        step: step_index,
+                        reason: "the clock can't go backwards".into(),
+                    });
+                }
+
+                now = now
+                    .checked_add(*seconds)
+                    .ok_or(reject(ErrorCodes::MathOverflow))?;
+            }
+
+            Step::Repay { offer } => {
+                let offer_state = open_offer(&mut offers, *offer, step_index)?;
+                let mut loan = offer_state
+                    .loan
+                    .take()
+                    .ok_or(reject(ErrorCodes::LoanAlreadyRepaid))?;
+                let repay_amount = offer_state.account.repay_lamport_amount;
+
+                let repayment =
+                    lifecycle::repay(&mut loan.account, &mut offer_state.account, &mut pool)
+                        .map_err(program_error)?;
+
+                credit(&mut balances, &loan.borrower, -(repay_amount as i128));
+                credit(&mut balances, &offer_state.lender, repayment.payout as i128);
+
+                // without auto-relend the offer stays taken for good
+                offer_state.is_closed = !offer_state.account.auto_relend;
+                offer_state.vault = repayment.relend_amount;
+            }
+
+            Step::Liquidate { offer } => {
+                let offer_state = open_offer(&mut offers, *offer, step_index)?;
+
+                let Some(loan) = offer_state.loan.as_mut() else {
+                    return Err(reject(ErrorCodes::LoanAlreadyRepaid));
+                };
+
+                lifecycle::liquidate(&mut loan.account, &offer_state.account, &mut pool, now)
+                    .map_err(program_error)?;
+
+                liquidations.push(Liquidation {
+                    offer: *offer,
+                    lender: offer_state.lender.clone(),
+                    borrower: loan.borrower.clone(),
+                    principal: offer_state.account.offer_lamport_amount,
+                    liquidated_at: now,
+                });
+
+                offer_state.loan = None;
+                offer_state.is_closed = true;
+            }
+        }
+    }
+
+    Ok(Report {
+        now,
+        balances,
+        vaults: offers.iter().map(|offer| offer.vault).collect(),
+        liquidations,
+        pool: PoolSummary::from(&pool),
+    })
+}
+
+fn open_offer(
+    offers: &mut [SimOffer],
+    offer: usize,
+    step: usize,
+) -> Result<&mut SimOffer, SimError> {
+    match offers.get_mut(offer) {
+        Some(offer_state) if !offer_state.is_closed => Ok(offer_state),
+        Some(_) => Err(SimError::Rejected {
+            step,
+            reason: format!("offer {} is closed", offer),
+        }),
+        None => Err(SimError::Rejected {
+            step,
+            reason: format!("offer {} does not exist", offer),
+        }),
+    }
+}
+
+fn credit(balances: &mut BTreeMap<String, i128>, who: &str, lamports: i128) {
+    *balances.entry(who.to_string()).or_default() += lamports;
+}
+
+fn rejected(step: usize, error: ErrorCodes) -> SimError {
+    SimError::Rejected {
+        step,
+        reason: error.to_string(),
+    }
+}
+
+fn rejected_by_program(step: usize, error: Error) -> SimError {
+    let reason = match error {
+        Error::AnchorError(error) => error.error_msg,
+        Error::ProgramError(error) => error.to_string(),
+    };
+
+    SimError::Rejected { step, reason }
+}
+
+/// Account as `init` hands it to a handler, every field zeroed
+fn zeroed<T: AnchorDeserialize>(space: usize) -> T {
+    T::deserialize(&mut &vec![0; space - 8][..]).expect("zeroed accounts always deserialise")
+}
+
+/// Stable key standing in for the wallet of a named participant
+fn participant(name: &str) -> Pubkey {
+    Pubkey::new_from_array(hash(name.as_bytes()).to_bytes())
+}
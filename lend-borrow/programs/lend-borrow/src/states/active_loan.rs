@@ -1,5 +1,9 @@
 use anchor_lang::prelude::*;
 
+use super::Versioned;
+use crate::errors::ErrorCodes;
+use crate::math;
+
 #[account]
 pub struct ActiveLoan {
     /// Collection
@@ -31,8 +35,77 @@ pub struct ActiveLoan {
 
     /// Bump
     pub bump: u8,
+
+    /// Account Layout Version
+    pub version: u8,
+
+    /// Mints of every NFT backing the loan, starting with `mint`
+    pub collateral_mints: Vec<Pubkey>,
+
+    /// Vault token accounts holding each of `collateral_mints`
+    pub collateral_vaults: Vec<Pubkey>,
+
+    /// Reserved for future fields
+    pub reserved: [u8; 128],
 }
 
 impl ActiveLoan {
-    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 32 + 8 + 8 + 1 + 1 + 1;
+    pub const VERSION: u8 = 1;
+
+    /// Maximum number of NFTs a single loan can be backed by
+    pub const MAX_COLLATERAL: usize = 5;
+
+    /// 4 bytes for the length prefix plus 32 bytes for every pubkey in each collateral list
+    const COLLATERAL_LIST_LEN: usize = 4 + 32 * Self::MAX_COLLATERAL;
+
+    pub const LEN: usize =
+        8 + 32 + 32 + 32 + 32 + 32 + 8 + 8 + 1 + 1 + 1 + 1 + Self::COLLATERAL_LIST_LEN * 2 + 128;
+
+    /// Loan is neither repaid nor liquidated
+    pub fn is_outstanding(&self) -> bool {
+        !self.is_repaid && !self.is_liquidated
+    }
+
+    /// Fails unless the loan is still outstanding
+    pub fn require_outstanding(&self) -> Result<()> {
+        if self.is_repaid {
+            return Err(ErrorCodes::LoanAlreadyRepaid.into());
+        }
+
+        if self.is_liquidated {
+            return Err(ErrorCodes::LoanAlreadyLiquidated.into());
+        }
+
+        Ok(())
+    }
+
+    /// Vault token account holding `mint`. Loans migrated from before bundles
+    /// don't record it and always kept the NFT at the offer's `vault-asset-account`
+    pub fn primary_vault(&self, program_id: &Pubkey) -> Pubkey {
+        match self.collateral_vaults.first() {
+            Some(vault) => *vault,
+            None => {
+                let seeds = [b"vault-asset-account".as_ref(), self.offer_account.as_ref()];
+                Pubkey::find_program_address(&seeds, program_id).0
+            }
+        }
+    }
+
+    /// Loan is outstanding and its repayment time is over
+    pub fn is_liquidatable(&self, now: i64) -> bool {
+        self.is_outstanding() && math::is_liquidatable(self.repay_ts, now)
+    }
+}
+
+impl Versioned for ActiveLoan {
+    const VERSION: u8 = ActiveLoan::VERSION;
+    const SPACE: usize = ActiveLoan::LEN;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
 }
@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
 
+use super::Versioned;
+use crate::errors::ErrorCodes;
+
 #[account]
 pub struct CollectionPool {
     /// NFT Collection ID
@@ -16,13 +19,138 @@ pub struct CollectionPool {
 
     /// Bump
     pub bump: u8,
+
+    /// Account Layout Version
+    pub version: u8,
+
+    /// Lamports in offers still open for borrowing
+    pub offered_liquidity: u64,
+
+    /// Principal of loans neither repaid nor liquidated
+    pub outstanding_principal: u64,
+
+    /// Number of loans neither repaid nor liquidated
+    pub active_loans: u64,
+
+    /// Number of loans ever taken from the pool
+    pub total_borrows: u64,
+
+    /// Interest paid to lenders over the lifetime of the pool
+    pub cumulative_interest_paid: u64,
+
+    /// Number of liquidated loans
+    pub default_count: u64,
+
+    /// Principal of liquidated loans
+    pub defaulted_principal: u64,
+
+    /// Reserved for future fields
+    pub reserved: [u8; 72],
 }
 
 impl CollectionPool {
+    pub const VERSION: u8 = 1;
+
     /// Number of bytes required to store a Collection pool account
     /// 8 bytes for the Discriminator - for anchor to create a unique id for the account
     /// 32 bytes each for collection id and pool owner (both pubKey types)
     /// 8 bytes each for duration and total offers (i64 and u64)
-    /// 1 byte for bump (u8)
-    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 1;
+    /// 1 byte each for bump and version (u8)
+    /// 8 bytes each for the seven aggregates (u64)
+    /// 72 bytes reserved for fields added in later versions
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 1 + 1 + 8 * 7 + 72;
+
+    // Pools migrated from before versioning start their aggregates at zero while loans
+    // may already be running, so decrements saturate instead of failing the
+    // handler that settles the loan.
+
+    /// A lender offered `amount` lamports
+    pub fn record_offer(&mut self, amount: u64) -> Result<()> {
+        self.offered_liquidity = self
+            .offered_liquidity
+            .checked_add(amount)
+            .ok_or(ErrorCodes::MathOverflow)?;
+
+        Ok(())
+    }
+
+    /// An open offer of `amount` lamports was withdrawn or cleaned up
+    pub fn record_offer_closed(&mut self, amount: u64) {
+        self.offered_liquidity = self.offered_liquidity.saturating_sub(amount);
+    }
+
+    /// An offer of `principal` lamports was borrowed
+    pub fn record_borrow(&mut self, principal: u64) -> Result<()> {
+        self.offered_liquidity = self.offered_liquidity.saturating_sub(principal);
+        self.outstanding_principal = self
+            .outstanding_principal
+            .checked_add(principal)
+            .ok_or(ErrorCodes::MathOverflow)?;
+        self.active_loans = self
+            .active_loans
+            .checked_add(1)
+            .ok_or(ErrorCodes::MathOverflow)?;
+        self.total_borrows = self
+            .total_borrows
+            .checked_add(1)
+            .ok_or(ErrorCodes::MathOverflow)?;
+
+        Ok(())
+    }
+
+    /// A loan of `principal` lamports was repaid with `repay_amount` lamports
+    pub fn record_repayment(&mut self, principal: u64, repay_amount: u64) -> Result<()> {
+        let interest = repay_amount
+            .checked_sub(principal)
+            .ok_or(ErrorCodes::MathOverflow)?;
+
+        self.outstanding_principal = self.outstanding_principal.saturating_sub(principal);
+        self.active_loans = self.active_loans.saturating_sub(1);
+        self.cumulative_interest_paid = self
+            .cumulative_interest_paid
+            .checked_add(interest)
+            .ok_or(ErrorCodes::MathOverflow)?;
+
+        Ok(())
+    }
+
+    /// A loan of `principal` lamports was liquidated
+    pub fn record_default(&mut self, principal: u64) -> Result<()> {
+        self.outstanding_principal = self.outstanding_principal.saturating_sub(principal);
+        self.active_loans = self.active_loans.saturating_sub(1);
+        self.default_count = self
+            .default_count
+            .checked_add(1)
+            .ok_or(ErrorCodes::MathOverflow)?;
+        self.defaulted_principal = self
+            .defaulted_principal
+            .checked_add(principal)
+            .ok_or(ErrorCodes::MathOverflow)?;
+
+        Ok(())
+    }
+
+    /// The principal of an active loan was renegotiated from `previous` to `current` lamports
+    pub fn record_principal_change(&mut self, previous: u64, current: u64) -> Result<()> {
+        self.outstanding_principal = self
+            .outstanding_principal
+            .saturating_sub(previous)
+            .checked_add(current)
+            .ok_or(ErrorCodes::MathOverflow)?;
+
+        Ok(())
+    }
+}
+
+impl Versioned for CollectionPool {
+    const VERSION: u8 = CollectionPool::VERSION;
+    const SPACE: usize = CollectionPool::LEN;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
 }
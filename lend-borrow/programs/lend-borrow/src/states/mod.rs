@@ -1,9 +1,13 @@
 pub mod active_loan;
 pub mod collection_pool;
 pub mod offer;
+pub mod terms_proposal;
 pub mod vault;
+pub mod versioned;
 
 pub use active_loan::*;
 pub use collection_pool::*;
 pub use offer::*;
+pub use terms_proposal::*;
 pub use vault::*;
+pub use versioned::*;
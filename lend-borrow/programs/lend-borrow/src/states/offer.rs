@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use super::Versioned;
+
 #[account]
 pub struct Offer {
     /// Collection
@@ -22,8 +24,57 @@ pub struct Offer {
 
     /// Bump
     pub bump: u8,
+
+    /// Account Layout Version
+    pub version: u8,
+
+    /// Offer Expiry Timestamp
+    pub expires_at: i64,
+
+    /// Number of NFTs the borrower has to post
+    pub collateral_count: u8,
+
+    /// Reopen the offer with the repaid lamports after a repayment
+    pub auto_relend: bool,
+
+    /// Maximum offer amount the repaid lamports compound up to
+    pub relend_cap: u64,
+
+    /// Reserved for future fields
+    pub reserved: [u8; 118],
 }
 
 impl Offer {
-    pub const LEN: usize = 8 + 32 + 8 + 8 + 32 + 1 + 32 + 1;
+    pub const VERSION: u8 = 1;
+
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 32 + 1 + 32 + 1 + 1 + 8 + 1 + 1 + 8 + 118;
+
+    /// Number of NFTs backing a loan against this offer,
+    /// offers from before bundles existed read as zero and take a single NFT.
+    pub fn required_collateral(&self) -> usize {
+        self.collateral_count.max(1) as usize
+    }
+
+    /// Offer can no longer be borrowed at `now`
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at <= now
+    }
+}
+
+impl Versioned for Offer {
+    const VERSION: u8 = Offer::VERSION;
+    const SPACE: usize = Offer::LEN;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+
+    fn upgrade_unversioned(&mut self) {
+        // offers from before expiry timestamps never expire
+        self.expires_at = i64::MAX;
+    }
 }
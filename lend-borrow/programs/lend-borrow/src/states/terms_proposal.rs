@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+use super::Versioned;
+
+#[account]
+pub struct TermsProposal {
+    /// Active Loan the new terms apply to
+    pub active_loan: Pubkey,
+
+    /// Lender or borrower who proposed the terms
+    pub proposer: Pubkey,
+
+    /// Proposed Offer Amount
+    pub offer_lamport_amount: u64,
+
+    /// Proposed Repay Amount
+    pub repay_lamport_amount: u64,
+
+    /// Proposed Repayment Timestamp
+    pub repay_ts: i64,
+
+    /// Lamports the proposer locked in this account to settle a change of principal
+    pub escrowed_lamports: u64,
+
+    /// Bump
+    pub bump: u8,
+
+    /// Account Layout Version
+    pub version: u8,
+
+    /// Start of the loan the terms were proposed for, the loan account is
+    /// recreated at the same address when its offer relends
+    pub loan_ts: i64,
+
+    /// Borrower of the loan the terms were proposed for
+    pub borrower: Pubkey,
+
+    /// Reserved for future fields
+    pub reserved: [u8; 24],
+}
+
+impl TermsProposal {
+    pub const VERSION: u8 = 1;
+
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 1 + 1 + 8 + 32 + 24;
+}
+
+impl Versioned for TermsProposal {
+    const VERSION: u8 = TermsProposal::VERSION;
+    const SPACE: usize = TermsProposal::LEN;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+}
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use super::Versioned;
+
 #[account]
 pub struct Vault {
     /// The offer this vault is linked to
@@ -7,8 +9,29 @@ pub struct Vault {
 
     /// Bump
     pub bump: u8,
+
+    /// Account Layout Version
+    pub version: u8,
+
+    /// Reserved for future fields
+    pub reserved: [u8; 64],
 }
 
 impl Vault {
-    pub const LEN: usize = 8 + 32 + 1;
+    pub const VERSION: u8 = 1;
+
+    pub const LEN: usize = 8 + 32 + 1 + 1 + 64;
+}
+
+impl Versioned for Vault {
+    const VERSION: u8 = Vault::VERSION;
+    const SPACE: usize = Vault::LEN;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
 }
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+/// Implemented by every account of the program so that accounts created
+/// with an older layout can be upgraded in place by the `migrate_*` instructions.
+///
+/// New fields are appended after the fields of earlier layouts, taking the place
+/// of reserved padding where they fit and growing the account otherwise. A
+/// migrated account is zero extended to `SPACE`, so it reads new fields as zero
+/// unless `upgrade_unversioned` gives them another starting value.
+pub trait Versioned: AccountSerialize + AccountDeserialize + Discriminator {
+    /// Layout version written by this build of the program
+    const VERSION: u8;
+
+    /// Number of bytes required to store the account at the current version
+    const SPACE: usize;
+
+    fn version(&self) -> u8;
+
+    fn set_version(&mut self, version: u8);
+
+    /// Sets the fields an account created before versioning reads as zero
+    /// to their starting values.
+    fn upgrade_unversioned(&mut self) {}
+}
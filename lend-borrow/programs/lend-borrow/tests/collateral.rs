@@ -0,0 +1,84 @@
+//! Collection membership of bundled collateral.
+
+use anchor_lang::error::Error;
+use anchor_lang::prelude::{AccountInfo, Owner, Pubkey};
+use anchor_spl::metadata::mpl_token_metadata::types::Key;
+use anchor_spl::metadata::MetadataAccount;
+use lend_borrow::collateral::require_collection_member;
+use lend_borrow::ErrorCodes;
+
+// Borsh layout of a MetadataV1 account with empty strings and no optional fields
+// besides its collection.
+fn metadata(mint: &Pubkey, collection: Option<(&Pubkey, bool)>) -> Vec<u8> {
+    let mut data = vec![Key::MetadataV1 as u8];
+    data.extend_from_slice(Pubkey::new_unique().as_ref());
+    data.extend_from_slice(mint.as_ref());
+    // name, symbol and uri
+    data.extend_from_slice(&[0; 4 * 3]);
+    data.extend_from_slice(&0u16.to_le_bytes());
+    // creators, primary sale, mutable, edition nonce and token standard
+    data.extend_from_slice(&[0, 0, 1, 0, 0]);
+    match collection {
+        Some((key, verified)) => {
+            data.extend_from_slice(&[1, verified as u8]);
+            data.extend_from_slice(key.as_ref());
+        }
+        None => data.push(0),
+    }
+    // uses, collection details and programmable config
+    data.extend_from_slice(&[0, 0, 0]);
+
+    data
+}
+
+fn check(owner: Pubkey, data: Vec<u8>, mint: &Pubkey, collection: &Pubkey) -> Result<(), Error> {
+    let key = Pubkey::new_unique();
+    let (mut lamports, mut data) = (1, data);
+    let info = AccountInfo::new(
+        &key,
+        false,
+        false,
+        &mut lamports,
+        &mut data,
+        &owner,
+        false,
+        0,
+    );
+
+    require_collection_member(&info, mint, collection)
+}
+
+#[test]
+fn bundled_nfts_are_verified_members_of_the_collection() {
+    let (mint, collection) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let metadata_program = MetadataAccount::owner();
+    let not_a_member: Error = ErrorCodes::NotCollectionMember.into();
+
+    let verified = metadata(&mint, Some((&collection, true)));
+    assert!(check(metadata_program, verified.clone(), &mint, &collection).is_ok());
+
+    // unverified members, members of another collection and NFTs outside any collection
+    for data in [
+        metadata(&mint, Some((&collection, false))),
+        metadata(&mint, Some((&Pubkey::new_unique(), true))),
+        metadata(&mint, None),
+    ] {
+        assert_eq!(
+            check(metadata_program, data, &mint, &collection).unwrap_err(),
+            not_a_member
+        );
+    }
+
+    // metadata of another mint, or not written by the metadata program
+    assert_eq!(
+        check(
+            metadata_program,
+            verified.clone(),
+            &Pubkey::new_unique(),
+            &collection
+        )
+        .unwrap_err(),
+        not_a_member
+    );
+    assert!(check(Pubkey::new_unique(), verified, &mint, &collection).is_err());
+}
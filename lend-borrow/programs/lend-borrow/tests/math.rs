@@ -0,0 +1,116 @@
+//! Property tests feeding extreme amounts and durations to the loan math.
+
+use lend_borrow::math::{
+    is_liquidatable, relend_split, repay_amount, repay_amount_at_rate, repay_timestamp,
+    seconds_until_liquidatable, share_bps, BPS_DENOMINATOR, INTEREST_PERCENT,
+};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn repay_amount_matches_wide_arithmetic(offer_amount in any::<u64>()) {
+        let expected = offer_amount as u128 + offer_amount as u128 * INTEREST_PERCENT as u128 / 100;
+
+        match repay_amount(offer_amount) {
+            Ok(amount) => prop_assert_eq!(amount as u128, expected),
+            // only amounts whose interest cannot be computed in u64 are rejected
+            Err(_) => prop_assert!(
+                offer_amount.checked_mul(INTEREST_PERCENT).is_none() || expected > u64::MAX as u128
+            ),
+        }
+    }
+
+    #[test]
+    fn repay_amount_never_below_offer(offer_amount in 0..=u64::MAX / INTEREST_PERCENT) {
+        let amount = repay_amount(offer_amount).unwrap();
+
+        prop_assert!(amount >= offer_amount);
+    }
+
+    #[test]
+    fn repay_amount_at_rate_matches_wide_arithmetic(
+        offer_amount in any::<u64>(),
+        interest_bps in any::<u64>(),
+    ) {
+        let expected = offer_amount as u128
+            + offer_amount as u128 * interest_bps as u128 / BPS_DENOMINATOR as u128;
+
+        match repay_amount_at_rate(offer_amount, interest_bps) {
+            Ok(amount) => prop_assert_eq!(amount as u128, expected),
+            Err(_) => prop_assert!(expected > u64::MAX as u128),
+        }
+    }
+
+    #[test]
+    fn repay_amount_at_default_rate_matches_repay_amount(
+        offer_amount in 0..=u64::MAX / INTEREST_PERCENT,
+    ) {
+        prop_assert_eq!(
+            repay_amount_at_rate(offer_amount, INTEREST_PERCENT * 100).unwrap(),
+            repay_amount(offer_amount).unwrap()
+        );
+    }
+
+    #[test]
+    fn relend_split_conserves_repaid_lamports(repay_amount in any::<u64>(), relend_cap in any::<u64>()) {
+        let (relend_amount, payout) = relend_split(repay_amount, relend_cap);
+
+        prop_assert!(relend_amount <= relend_cap);
+        prop_assert_eq!(relend_amount as u128 + payout as u128, repay_amount as u128);
+    }
+
+    #[test]
+    fn repay_timestamp_matches_wide_arithmetic(loan_ts in any::<i64>(), duration in any::<i64>()) {
+        let expected = loan_ts as i128 + duration as i128;
+
+        match repay_timestamp(loan_ts, duration) {
+            Ok(repay_ts) => prop_assert_eq!(repay_ts as i128, expected),
+            Err(_) => prop_assert!(expected > i64::MAX as i128 || expected < i64::MIN as i128),
+        }
+    }
+
+    #[test]
+    fn repay_timestamp_after_loan_for_positive_durations(
+        loan_ts in 0..i64::MAX / 2,
+        duration in 1..i64::MAX / 2,
+    ) {
+        prop_assert!(repay_timestamp(loan_ts, duration).unwrap() > loan_ts);
+    }
+}
+
+#[test]
+fn repay_amount_rejects_overflowing_offers() {
+    assert!(repay_amount(u64::MAX).is_err());
+    assert!(repay_amount(u64::MAX / INTEREST_PERCENT + 1).is_err());
+}
+
+#[test]
+fn repay_amount_adds_interest() {
+    assert_eq!(repay_amount(0).unwrap(), 0);
+    assert_eq!(repay_amount(2_000_000_000).unwrap(), 2_200_000_000);
+}
+
+#[test]
+fn repay_timestamp_rejects_overflow() {
+    assert!(repay_timestamp(i64::MAX, 1).is_err());
+    assert!(repay_timestamp(i64::MIN, -1).is_err());
+}
+
+#[test]
+fn loan_becomes_liquidatable_after_repay_timestamp() {
+    assert!(!is_liquidatable(100, 99));
+    assert!(!is_liquidatable(100, 100));
+    assert!(is_liquidatable(100, 101));
+
+    assert_eq!(seconds_until_liquidatable(100, 99), 2);
+    assert_eq!(seconds_until_liquidatable(100, 100), 1);
+    assert_eq!(seconds_until_liquidatable(100, 101), 0);
+    assert_eq!(seconds_until_liquidatable(i64::MAX, i64::MIN), i64::MAX);
+}
+
+#[test]
+fn share_bps_handles_empty_and_full_pools() {
+    assert_eq!(share_bps(0, 0), 0);
+    assert_eq!(share_bps(1, 4), 2_500);
+    assert_eq!(share_bps(u64::MAX, u64::MAX), BPS_DENOMINATOR);
+}
@@ -0,0 +1,74 @@
+//! Migrations of accounts written by earlier layouts.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AccountDeserialize, Discriminator};
+use lend_borrow::instructions::migrate::upgrade;
+use lend_borrow::states::{CollectionPool, Offer};
+
+// Offer as written before expiry timestamps and versioning existed.
+fn unversioned_offer(collection: &Pubkey, lender: &Pubkey, borrower: &Pubkey) -> Vec<u8> {
+    let mut data = Offer::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(collection.as_ref());
+    data.extend_from_slice(&1_000u64.to_le_bytes());
+    data.extend_from_slice(&1_100u64.to_le_bytes());
+    data.extend_from_slice(lender.as_ref());
+    data.push(1);
+    data.extend_from_slice(borrower.as_ref());
+    data.push(254);
+
+    data
+}
+
+#[test]
+fn unversioned_offer_keeps_its_fields_and_never_expires() {
+    let (collection, lender, borrower) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+
+    let mut data = unversioned_offer(&collection, &lender, &borrower);
+    data.resize(Offer::LEN, 0);
+    upgrade::<Offer>(&mut data).unwrap();
+
+    let offer = Offer::try_deserialize(&mut &data[..]).unwrap();
+    assert_eq!(offer.collection, collection);
+    assert_eq!(offer.offer_lamport_amount, 1_000);
+    assert_eq!(offer.repay_lamport_amount, 1_100);
+    assert_eq!(offer.lender, lender);
+    assert!(offer.is_loan_taken);
+    assert_eq!(offer.borrower, borrower);
+    assert_eq!(offer.bump, 254);
+    assert_eq!(offer.expires_at, i64::MAX);
+    assert_eq!(offer.version, Offer::VERSION);
+    assert_eq!(offer.required_collateral(), 1);
+    assert!(!offer.auto_relend);
+
+    assert!(upgrade::<Offer>(&mut data).is_err());
+}
+
+#[test]
+fn unversioned_pool_starts_its_aggregates_at_zero() {
+    let (collection, owner) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    let mut data = CollectionPool::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(collection.as_ref());
+    data.extend_from_slice(owner.as_ref());
+    data.extend_from_slice(&86_400i64.to_le_bytes());
+    data.extend_from_slice(&3u64.to_le_bytes());
+    data.push(253);
+    data.resize(CollectionPool::LEN, 0);
+    upgrade::<CollectionPool>(&mut data).unwrap();
+
+    let pool = CollectionPool::try_deserialize(&mut &data[..]).unwrap();
+    assert_eq!(pool.collection_id, collection);
+    assert_eq!(pool.pool_owner, owner);
+    assert_eq!(pool.duration, 86_400);
+    assert_eq!(pool.total_offers, 3);
+    assert_eq!(pool.bump, 253);
+    assert_eq!(pool.version, CollectionPool::VERSION);
+    assert_eq!(pool.offered_liquidity, 0);
+    assert_eq!(pool.active_loans, 0);
+
+    assert!(upgrade::<CollectionPool>(&mut data).is_err());
+}
@@ -0,0 +1,162 @@
+//! Scenarios replayed through the offline simulator.
+
+use lend_borrow::math::repay_amount;
+use lend_borrow::sim::{run, Scenario, SimError, Step};
+
+const SOL: u64 = 1_000_000_000;
+
+#[test]
+fn repaid_loan_pays_interest_to_lender() {
+    let scenario = Scenario::from_csv(
+        "
+        # one week loans
+        pool,604800,1000
+        offer,alice,2000000000,90000
+        borrow,0,bob
+        advance,3600
+        repay,0
+        ",
+    )
+    .unwrap();
+
+    let report = run(&scenario).unwrap();
+    let interest = repay_amount(2 * SOL).unwrap() - 2 * SOL;
+
+    assert_eq!(report.now, 4_600);
+    assert_eq!(report.balances["alice"], interest as i128);
+    assert_eq!(report.balances["bob"], -(interest as i128));
+    assert_eq!(report.pool.cumulative_interest_paid, interest);
+    assert_eq!(report.pool.active_loans, 0);
+    assert_eq!(report.pool.outstanding_principal, 0);
+    assert!(report.liquidations.is_empty());
+}
+
+#[test]
+fn overdue_loan_is_liquidated() {
+    let scenario = Scenario::from_csv(
+        "
+        pool,10
+        offer,alice,1000,100
+        offer,carol,3000,100
+        borrow,0,bob
+        borrow,1,bob
+        advance,10
+        repay,1
+        advance,1
+        liquidate,0
+        ",
+    )
+    .unwrap();
+
+    let report = run(&scenario).unwrap();
+
+    assert_eq!(report.liquidations.len(), 1);
+    assert_eq!(report.liquidations[0].borrower, "bob");
+    assert_eq!(report.liquidations[0].principal, 1_000);
+    assert_eq!(report.pool.default_count, 1);
+    assert_eq!(report.pool.defaulted_principal, 1_000);
+    assert_eq!(report.pool.total_borrows, 2);
+    assert_eq!(report.pool.default_rate_bps, 5_000);
+    assert_eq!(report.balances["alice"], -1_000);
+}
+
+#[test]
+fn liquidating_on_repay_timestamp_is_rejected() {
+    let scenario = Scenario::from_csv(
+        "
+        pool,10
+        offer,alice,1000,100
+        borrow,0,bob
+        advance,10
+        liquidate,0
+        ",
+    )
+    .unwrap();
+
+    assert_eq!(
+        run(&scenario),
+        Err(SimError::Rejected {
+            step: 3,
+            reason: "Cannot Liquidate Loan Yet".into(),
+        })
+    );
+}
+
+#[test]
+fn expired_offer_cannot_be_borrowed_but_can_be_cleaned_up() {
+    let mut scenario = Scenario::from_csv(
+        "
+        pool,10
+        offer,alice,1000,5
+        advance,5
+        borrow,0,bob
+        ",
+    )
+    .unwrap();
+
+    assert_eq!(
+        run(&scenario),
+        Err(SimError::Rejected {
+            step: 2,
+            reason: "Offer Expired".into(),
+        })
+    );
+
+    scenario.steps[2] = Step::Cleanup { offer: 0 };
+
+    let report = run(&scenario).unwrap();
+
+    assert_eq!(report.balances["alice"], 0);
+    assert_eq!(report.pool.offered_liquidity, 0);
+    assert_eq!(report.pool.total_offers, 0);
+}
+
+#[test]
+fn auto_relend_compounds_up_to_cap() {
+    let scenario = Scenario::from_json(
+        r#"{
+            "duration": 100,
+            "steps": [
+                { "action": "offer", "lender": "alice", "amount": 1000, "expires_at": 1000, "relend_cap": 1050 },
+                { "action": "borrow", "offer": 0, "borrower": "bob" },
+                { "action": "repay", "offer": 0 },
+                { "action": "borrow", "offer": 0, "borrower": "dave" }
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    let report = run(&scenario).unwrap();
+
+    // 1100 repaid, 1050 offered again and 50 paid out to the lender
+    assert_eq!(report.balances["alice"], -1_000 + 50);
+    assert_eq!(report.balances["dave"], 1_050);
+    assert_eq!(report.pool.outstanding_principal, 1_050);
+    assert_eq!(report.pool.total_borrows, 2);
+    assert_eq!(report.vaults, vec![0]);
+}
+
+#[test]
+fn csv_and_json_scenarios_are_equivalent() {
+    let csv = Scenario::from_csv("pool,60,5\noffer,alice,10,100,20\nborrow,0,bob\n").unwrap();
+    let json = Scenario::from_json(&serde_json::to_string(&csv).unwrap()).unwrap();
+
+    assert_eq!(csv, json);
+    assert_eq!(run(&csv).unwrap().to_json(), run(&json).unwrap().to_json());
+}
+
+#[test]
+fn malformed_csv_reports_line() {
+    assert!(matches!(
+        Scenario::from_csv("pool,60\noffer,alice,lots,100\n"),
+        Err(SimError::Parse { line: 2, .. })
+    ));
+    assert!(matches!(
+        Scenario::from_csv("offer,alice,10,100\n"),
+        Err(SimError::Parse { line: 1, .. })
+    ));
+    assert!(matches!(
+        Scenario::from_csv("pool,60\nteleport,0\n"),
+        Err(SimError::Parse { line: 2, .. })
+    ));
+}
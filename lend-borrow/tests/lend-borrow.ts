@@ -13,6 +13,12 @@ import {
   getAccount,
   mintTo,
 } from "@solana/spl-token";
+import {
+  PROGRAM_ID as TOKEN_METADATA_PROGRAM_ID,
+  createCreateMasterEditionV3Instruction,
+  createCreateMetadataAccountV3Instruction,
+  createVerifyCollectionInstruction,
+} from "@metaplex-foundation/mpl-token-metadata";
 import { NftLendBorrow } from "../target/types/nft_lend_borrow";
 import { assert } from "chai";
 
@@ -45,6 +51,9 @@ describe("lend-borrow", () => {
   let vaultPDA: PublicKey;
   let vaultAuthorityPDA: PublicKey;
 
+  // Vault::LEN, the vault keeps this much rent when its lamports are handed out
+  let vaultAccountSize = 8 + 32 + 1 + 1 + 64;
+
   let collectionId = new PublicKey(
     "J1S9H3QjnRtBbbuD4HjPV6RpRhwuk4zKbxsnCHuTgh9w"
   );
@@ -180,6 +189,10 @@ describe("lend-borrow", () => {
 
   let totalOffers = 0;
   let offerAmount = new anchor.BN(2 * LAMPORTS_PER_SOL);
+  let offerLifetime = 60 * 60; // offers stay open for an hour unless stated otherwise
+
+  const offerExpiry = (lifetime: number = offerLifetime) =>
+    new anchor.BN(Math.floor(Date.now() / 1000) + lifetime);
 
   it("Can offer loan", async () => {
     let [offer, _offerBump] = anchor.web3.PublicKey.findProgramAddressSync(
@@ -205,13 +218,14 @@ describe("lend-borrow", () => {
     vaultPDA = vault;
 
     await program.methods
-      .offerLoan(offerAmount)
+      .offerLoan(offerAmount, offerExpiry(), 1)
       .accounts({
         offerLoan: offerPDA,
         vaultAccount: vaultPDA,
         collectionPool: collectionPoolPDA,
         lender: lender.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
       })
       .signers([lender])
       .rpc();
@@ -280,7 +294,7 @@ describe("lend-borrow", () => {
     vaultAuthorityPDA = vaultAuth;
 
     const minimumBalanceForRentExemption =
-      await provider.connection.getMinimumBalanceForRentExemption(41);
+      await provider.connection.getMinimumBalanceForRentExemption(vaultAccountSize);
 
     await program.methods
       .borrow(new anchor.BN(minimumBalanceForRentExemption))
@@ -367,6 +381,51 @@ describe("lend-borrow", () => {
     assert.strictEqual(borrowerAssetTokenAccount.amount.toString(), "0");
   });
 
+  it("Can view loan status and pool summary", async () => {
+    const loanStatus = await program.methods
+      .getLoanStatus()
+      .accounts({
+        activeLoan: activeLoanPDA,
+        offer: offerPDA,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      })
+      .view();
+
+    const offerAccount = await program.account.offer.fetch(offerPDA);
+
+    assert.strictEqual(
+      loanStatus.principal.toNumber(),
+      offerAmount.toNumber()
+    );
+    assert.strictEqual(
+      loanStatus.amountDue.toNumber(),
+      offerAccount.repayLamportAmount.toNumber()
+    );
+    assert.isAbove(loanStatus.secondsUntilLiquidatable.toNumber(), 0);
+    assert.strictEqual(loanStatus.isLiquidatable, false);
+    assert.strictEqual(loanStatus.isRepaid, false);
+
+    const poolSummary = await program.methods
+      .getPoolSummary()
+      .accounts({
+        collectionPool: collectionPoolPDA,
+      })
+      .view();
+
+    assert.strictEqual(
+      poolSummary.collectionId.toBase58(),
+      collectionId.toBase58()
+    );
+    assert.strictEqual(poolSummary.totalOffers.toNumber(), totalOffers + 1);
+    assert.strictEqual(poolSummary.activeLoans.toNumber(), 1);
+    assert.strictEqual(
+      poolSummary.outstandingPrincipal.toNumber(),
+      offerAmount.toNumber()
+    );
+    assert.strictEqual(poolSummary.offeredLiquidity.toNumber(), 0);
+    assert.strictEqual(poolSummary.utilisationBps.toNumber(), 10_000);
+  });
+
   it("Can repay loan", async () => {
     await program.methods
       .repay()
@@ -449,13 +508,14 @@ describe("lend-borrow", () => {
     vaultPDA = vault;
 
     await program.methods
-      .offerLoan(offerAmount)
+      .offerLoan(offerAmount, offerExpiry(), 1)
       .accounts({
         offerLoan: offerPDA,
         vaultAccount: vaultPDA,
         collectionPool: collectionPoolPDA,
         lender: lender.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
       })
       .signers([lender])
       .rpc();
@@ -518,7 +578,7 @@ describe("lend-borrow", () => {
     vaultAssetAccount = vaultAsset;
 
     const minimumBalanceForRentExemption =
-      await provider.connection.getMinimumBalanceForRentExemption(41);
+      await provider.connection.getMinimumBalanceForRentExemption(vaultAccountSize);
     await program.methods
       .borrow(new anchor.BN(minimumBalanceForRentExemption))
       .accounts({
@@ -672,13 +732,14 @@ describe("lend-borrow", () => {
     vaultPDA = vault;
 
     await program.methods
-      .offerLoan(offerAmount)
+      .offerLoan(offerAmount, offerExpiry(), 1)
       .accounts({
         offerLoan: offerPDA,
         vaultAccount: vaultPDA,
         collectionPool: collectionPoolPDA,
         lender: lender.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
       })
       .signers([lender])
       .rpc();
@@ -714,7 +775,7 @@ describe("lend-borrow", () => {
     );
 
     const minimumBalanceForRentExemption =
-      await provider.connection.getMinimumBalanceForRentExemption(41);
+      await provider.connection.getMinimumBalanceForRentExemption(vaultAccountSize);
 
     await program.methods
       .withdrawOffer(new anchor.BN(minimumBalanceForRentExemption))
@@ -738,6 +799,810 @@ describe("lend-borrow", () => {
       0.5 * LAMPORTS_PER_SOL
     );
   });
+
+  it("Can clean up expired offer", async () => {
+    // offers are indexed by the pool's open offer count, which the withdrawal lowered
+    totalOffers = (
+      await program.account.collectionPool.fetch(collectionPoolPDA)
+    ).totalOffers.toNumber();
+    let [offer, _offerBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        anchor.utils.bytes.utf8.encode("offer"),
+        collectionPoolPDA.toBuffer(),
+        lender.publicKey.toBuffer(),
+        Buffer.from(totalOffers.toString()),
+      ],
+      program.programId
+    );
+    offerPDA = offer;
+
+    let [vault, _vaultBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        anchor.utils.bytes.utf8.encode("vault"),
+        collectionPoolPDA.toBuffer(),
+        lender.publicKey.toBuffer(),
+        Buffer.from(totalOffers.toString()),
+      ],
+      program.programId
+    );
+    vaultPDA = vault;
+
+    let shortLifetime = 5;
+
+    await program.methods
+      .offerLoan(offerAmount, offerExpiry(shortLifetime), 1)
+      .accounts({
+        offerLoan: offerPDA,
+        vaultAccount: vaultPDA,
+        collectionPool: collectionPoolPDA,
+        lender: lender.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      })
+      .signers([lender])
+      .rpc();
+
+    const createdOffer = await program.account.offer.fetch(offerPDA);
+
+    await sleep(createdOffer.expiresAt.toNumber() * 1000 - Date.now() + 3000);
+
+    // cleanup is permissionless, the provider wallet cranks it instead of the lender
+    await program.methods
+      .cleanupExpiredOffer()
+      .accounts({
+        offerLoan: offerPDA,
+        vaultAccount: vaultPDA,
+        collectionPool: collectionPoolPDA,
+        lender: lender.publicKey,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      })
+      .rpc();
+
+    const offerAccount = await provider.connection.getAccountInfo(offerPDA);
+    const vaultAccount = await provider.connection.getAccountInfo(vaultPDA);
+
+    assert.isNull(offerAccount);
+    assert.isNull(vaultAccount);
+
+    const lenderAccount = await provider.connection.getAccountInfo(
+      lender.publicKey
+    );
+
+    assert.approximately(
+      lenderAccount.lamports,
+      lenderInitialBalance - offerAmount.toNumber(),
+      0.5 * LAMPORTS_PER_SOL
+    );
+  });
+
+  // Mints a new NFT into a token account of `owner`.
+  const mintNft = async (owner: PublicKey) => {
+    const mint = await createMint(
+      provider.connection,
+      payer,
+      mintAuthority.publicKey,
+      undefined,
+      0,
+      undefined,
+      undefined,
+      TOKEN_PROGRAM_ID
+    );
+    const account = await createAccount(
+      provider.connection,
+      payer,
+      mint,
+      owner,
+      undefined,
+      undefined,
+      TOKEN_PROGRAM_ID
+    );
+    await mintTo(provider.connection, payer, mint, account, mintAuthority, 1);
+
+    return { mint, account };
+  };
+
+  // Opens an offer of `amount` lamports at the pool's next offer index.
+  const openOffer = async (amount: anchor.BN, collateralCount: number = 1) => {
+    const pool = await program.account.collectionPool.fetch(collectionPoolPDA);
+    const seeds = (prefix: string) => [
+      anchor.utils.bytes.utf8.encode(prefix),
+      collectionPoolPDA.toBuffer(),
+      lender.publicKey.toBuffer(),
+      Buffer.from(pool.totalOffers.toString()),
+    ];
+    const [offer] = PublicKey.findProgramAddressSync(
+      seeds("offer"),
+      program.programId
+    );
+    const [vault] = PublicKey.findProgramAddressSync(
+      seeds("vault"),
+      program.programId
+    );
+
+    await program.methods
+      .offerLoan(amount, offerExpiry(), collateralCount)
+      .accounts({
+        offerLoan: offer,
+        vaultAccount: vault,
+        collectionPool: collectionPoolPDA,
+        lender: lender.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      })
+      .signers([lender])
+      .rpc();
+
+    return { offer, vault };
+  };
+
+  // Borrows against `offer` with `nft`, bundle offers take their other NFTs
+  // through `remainingAccounts`.
+  const borrowAgainst = async (
+    offer: PublicKey,
+    vault: PublicKey,
+    nft: { mint: PublicKey; account: PublicKey },
+    remainingAccounts: anchor.web3.AccountMeta[] = []
+  ) => {
+    const [activeLoan] = PublicKey.findProgramAddressSync(
+      [anchor.utils.bytes.utf8.encode("active-loan"), offer.toBuffer()],
+      program.programId
+    );
+    const [vaultAsset] = PublicKey.findProgramAddressSync(
+      [anchor.utils.bytes.utf8.encode("vault-asset-account"), offer.toBuffer()],
+      program.programId
+    );
+
+    const minimumBalanceForRentExemption =
+      await provider.connection.getMinimumBalanceForRentExemption(
+        vaultAccountSize
+      );
+
+    await program.methods
+      .borrow(new anchor.BN(minimumBalanceForRentExemption))
+      .accounts({
+        activeLoan,
+        offerLoan: offer,
+        vaultAccount: vault,
+        vaultAssetAccount: vaultAsset,
+        vaultAuthority: vaultAuthorityPDA,
+        collectionPool: collectionPoolPDA,
+        borrower: borrower.publicKey,
+        borrowerAssetAccount: nft.account,
+        assetMint: nft.mint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      })
+      .remainingAccounts(remainingAccounts)
+      .signers([borrower])
+      .rpc();
+
+    return { activeLoan, vaultAsset };
+  };
+
+  // The borrower repays the loan against `offer` and gets `nft` back, bundle
+  // loans return their other NFTs through `remainingAccounts`.
+  const repayLoan = (
+    offer: PublicKey,
+    vault: PublicKey,
+    nft: { mint: PublicKey; account: PublicKey },
+    loan: { activeLoan: PublicKey; vaultAsset: PublicKey },
+    remainingAccounts: anchor.web3.AccountMeta[] = []
+  ) =>
+    program.methods
+      .repay()
+      .accounts({
+        activeLoan: loan.activeLoan,
+        offer,
+        collectionPool: collectionPoolPDA,
+        lender: lender.publicKey,
+        assetMint: nft.mint,
+        borrowerAssetAccount: nft.account,
+        vaultAssetAccount: loan.vaultAsset,
+        vaultAccount: vault,
+        vaultAuthority: vaultAuthorityPDA,
+        borrower: borrower.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts(remainingAccounts)
+      .signers([borrower])
+      .rpc();
+
+  const lamports = async (account: PublicKey) =>
+    (await provider.connection.getAccountInfo(account))?.lamports ?? 0;
+
+  // Runs `call` and checks the program rejected it with the error `code`.
+  const expectError = async (call: Promise<unknown>, code: string) => {
+    try {
+      await call;
+    } catch (err) {
+      assert.strictEqual(err.error?.errorCode?.code, code);
+      return;
+    }
+
+    assert.fail(`expected the program to fail with ${code}`);
+  };
+
+  it("Can renegotiate loan terms", async () => {
+    const principal = new anchor.BN(LAMPORTS_PER_SOL);
+    const { offer, vault } = await openOffer(principal);
+    const nft = await mintNft(borrower.publicKey);
+    const { activeLoan } = await borrowAgainst(offer, vault, nft);
+
+    const loan = await program.account.activeLoan.fetch(activeLoan);
+    const [proposal] = PublicKey.findProgramAddressSync(
+      [
+        anchor.utils.bytes.utf8.encode("terms-proposal"),
+        activeLoan.toBuffer(),
+        loan.loanTs.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+
+    const raised = new anchor.BN(1.5 * LAMPORTS_PER_SOL);
+    const interestBps = 500;
+    const repayTs = new anchor.BN(Math.floor(Date.now() / 1000) + 60 * 60);
+    const delta = raised.sub(principal).toNumber();
+
+    const propose = () =>
+      program.methods
+        .proposeTerms(raised, new anchor.BN(interestBps), repayTs)
+        .accounts({
+          termsProposal: proposal,
+          activeLoan,
+          offer,
+          proposer: lender.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        })
+        .signers([lender])
+        .rpc();
+
+    // the lender raising the principal locks the difference in the proposal
+    await propose();
+
+    const proposed = await program.account.termsProposal.fetch(proposal);
+    const proposalRent =
+      await provider.connection.getMinimumBalanceForRentExemption(
+        (await provider.connection.getAccountInfo(proposal)).data.length
+      );
+
+    assert.strictEqual(proposed.escrowedLamports.toNumber(), delta);
+    assert.strictEqual(await lamports(proposal), proposalRent + delta);
+
+    // cancelling hands the escrow and the rent back
+    const lenderBeforeCancel = await lamports(lender.publicKey);
+
+    await program.methods
+      .cancelTerms()
+      .accounts({ termsProposal: proposal, proposer: lender.publicKey })
+      .signers([lender])
+      .rpc();
+
+    assert.isNull(await provider.connection.getAccountInfo(proposal));
+    assert.strictEqual(
+      await lamports(lender.publicKey),
+      lenderBeforeCancel + proposalRent + delta
+    );
+
+    await propose();
+
+    const accept = (acceptor: anchor.web3.Keypair) =>
+      program.methods
+        .acceptTerms()
+        .accounts({
+          termsProposal: proposal,
+          activeLoan,
+          offer,
+          collectionPool: collectionPoolPDA,
+          proposer: lender.publicKey,
+          acceptor: acceptor.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        })
+        .signers([acceptor])
+        .rpc();
+
+    // only the counterparty can accept, neither the proposer nor an outsider
+    await expectError(accept(lender), "NotLoanParty");
+    await expectError(accept(payer), "NotLoanParty");
+
+    const borrowerBeforeAccept = await lamports(borrower.publicKey);
+    const poolBeforeAccept = await program.account.collectionPool.fetch(
+      collectionPoolPDA
+    );
+
+    await accept(borrower);
+
+    // the escrowed difference goes to the borrower along with the new terms
+    assert.strictEqual(
+      await lamports(borrower.publicKey),
+      borrowerBeforeAccept + delta
+    );
+    assert.isNull(await provider.connection.getAccountInfo(proposal));
+
+    const renegotiated = await program.account.offer.fetch(offer);
+
+    assert.strictEqual(
+      renegotiated.offerLamportAmount.toNumber(),
+      raised.toNumber()
+    );
+    assert.strictEqual(
+      renegotiated.repayLamportAmount.toNumber(),
+      raised.toNumber() + (raised.toNumber() * interestBps) / 10_000
+    );
+    assert.strictEqual(
+      (await program.account.activeLoan.fetch(activeLoan)).repayTs.toNumber(),
+      repayTs.toNumber()
+    );
+
+    const poolAfterAccept = await program.account.collectionPool.fetch(
+      collectionPoolPDA
+    );
+
+    assert.strictEqual(
+      poolAfterAccept.outstandingPrincipal.toNumber(),
+      poolBeforeAccept.outstandingPrincipal.toNumber() + delta
+    );
+  });
+
+  it("Can relend repaid lamports automatically", async () => {
+    const principal = new anchor.BN(LAMPORTS_PER_SOL);
+    const { offer, vault } = await openOffer(principal);
+
+    const configure = (
+      signer: anchor.web3.Keypair,
+      autoRelend: boolean,
+      cap: number
+    ) =>
+      program.methods
+        .configureAutoRelend(autoRelend, new anchor.BN(cap))
+        .accounts({ offerLoan: offer, lender: signer.publicKey })
+        .signers([signer])
+        .rpc();
+
+    // only the lender configures the offer, and relending needs a cap
+    await expectError(
+      configure(borrower, true, 2 * LAMPORTS_PER_SOL),
+      "ConstraintHasOne"
+    );
+    await expectError(configure(lender, true, 0), "InvalidRelendCap");
+
+    // the cap sits below the repay amount, the interest above it is paid out
+    const cap = 1_050_000_000;
+    await configure(lender, true, cap);
+
+    const nft = await mintNft(borrower.publicKey);
+    const loan = await borrowAgainst(offer, vault, nft);
+    const repayAmount = (
+      await program.account.offer.fetch(offer)
+    ).repayLamportAmount.toNumber();
+
+    const vaultBeforeRepay = await lamports(vault);
+    const lenderBeforeRepay = await lamports(lender.publicKey);
+    const poolBeforeRepay = await program.account.collectionPool.fetch(
+      collectionPoolPDA
+    );
+
+    await repayLoan(offer, vault, nft, loan);
+
+    assert.strictEqual(await lamports(vault), vaultBeforeRepay + cap);
+    assert.strictEqual(
+      await lamports(lender.publicKey),
+      lenderBeforeRepay + repayAmount - cap
+    );
+
+    // the loan and its vault token account close for the next borrow to recreate
+    assert.isNull(await provider.connection.getAccountInfo(loan.activeLoan));
+    assert.isNull(await provider.connection.getAccountInfo(loan.vaultAsset));
+    assert.strictEqual(
+      (await getAccount(provider.connection, nft.account)).amount.toString(),
+      "1"
+    );
+
+    const reopened = await program.account.offer.fetch(offer);
+
+    assert.strictEqual(reopened.isLoanTaken, false);
+    assert.strictEqual(
+      reopened.borrower.toBase58(),
+      PublicKey.default.toBase58()
+    );
+    assert.strictEqual(reopened.offerLamportAmount.toNumber(), cap);
+    assert.strictEqual(
+      reopened.repayLamportAmount.toNumber(),
+      cap + cap / 10
+    );
+
+    const poolAfterRepay = await program.account.collectionPool.fetch(
+      collectionPoolPDA
+    );
+
+    assert.strictEqual(
+      poolAfterRepay.offeredLiquidity.toNumber(),
+      poolBeforeRepay.offeredLiquidity.toNumber() + cap
+    );
+
+    // the reopened offer lends again, and once the lender opts out in the
+    // meantime the next repayment is paid out in full
+    const nextNft = await mintNft(borrower.publicKey);
+    const nextLoan = await borrowAgainst(offer, vault, nextNft);
+
+    await configure(lender, false, 0);
+
+    const lenderBeforeOptOut = await lamports(lender.publicKey);
+    const vaultBeforeOptOut = await lamports(vault);
+
+    await repayLoan(offer, vault, nextNft, nextLoan);
+
+    assert.strictEqual(
+      await lamports(lender.publicKey),
+      lenderBeforeOptOut + reopened.repayLamportAmount.toNumber()
+    );
+    assert.strictEqual(await lamports(vault), vaultBeforeOptOut);
+    assert.strictEqual(
+      (await program.account.activeLoan.fetch(nextLoan.activeLoan)).isRepaid,
+      true
+    );
+    assert.strictEqual(
+      (await program.account.offer.fetch(offer)).isLoanTaken,
+      true
+    );
+  });
+
+  it("Can repay loan on behalf of the borrower", async () => {
+    const { offer, vault } = await openOffer(new anchor.BN(LAMPORTS_PER_SOL));
+    const nft = await mintNft(borrower.publicKey);
+    const loan = await borrowAgainst(offer, vault, nft);
+
+    const friend = anchor.web3.Keypair.generate();
+
+    await provider.sendAndConfirm(
+      new Transaction().add(
+        SystemProgram.transfer({
+          fromPubkey: provider.wallet.publicKey,
+          toPubkey: friend.publicKey,
+          lamports: 2 * LAMPORTS_PER_SOL,
+        })
+      )
+    );
+
+    const repayOnBehalf = (recipient: PublicKey) =>
+      program.methods
+        .repayOnBehalf()
+        .accounts({
+          activeLoan: loan.activeLoan,
+          offer,
+          collectionPool: collectionPoolPDA,
+          lender: lender.publicKey,
+          assetMint: nft.mint,
+          borrowerAssetAccount: nft.account,
+          vaultAssetAccount: loan.vaultAsset,
+          vaultAccount: vault,
+          vaultAuthority: vaultAuthorityPDA,
+          borrower: recipient,
+          payer: friend.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([friend])
+        .rpc();
+
+    // the NFT can only go back to the borrower recorded on the loan
+    await expectError(repayOnBehalf(friend.publicKey), "ConstraintRaw");
+
+    const repayAmount = (
+      await program.account.offer.fetch(offer)
+    ).repayLamportAmount.toNumber();
+    const friendBeforeRepay = await lamports(friend.publicKey);
+    const lenderBeforeRepay = await lamports(lender.publicKey);
+    const borrowerBeforeRepay = await lamports(borrower.publicKey);
+
+    await repayOnBehalf(borrower.publicKey);
+
+    assert.strictEqual(
+      await lamports(friend.publicKey),
+      friendBeforeRepay - repayAmount
+    );
+    assert.strictEqual(
+      await lamports(lender.publicKey),
+      lenderBeforeRepay + repayAmount
+    );
+    assert.strictEqual(await lamports(borrower.publicKey), borrowerBeforeRepay);
+    assert.strictEqual(
+      (await program.account.activeLoan.fetch(loan.activeLoan)).isRepaid,
+      true
+    );
+    assert.strictEqual(
+      (await getAccount(provider.connection, nft.account)).amount.toString(),
+      "1"
+    );
+    const vaultAsset = await getAccount(provider.connection, loan.vaultAsset);
+
+    assert.strictEqual(vaultAsset.amount.toString(), "0");
+  });
+
+  const metadataAddress = (mint: PublicKey, ...suffix: Buffer[]) =>
+    PublicKey.findProgramAddressSync(
+      [
+        anchor.utils.bytes.utf8.encode("metadata"),
+        TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+        mint.toBuffer(),
+        ...suffix,
+      ],
+      TOKEN_METADATA_PROGRAM_ID
+    )[0];
+
+  type Collection = {
+    mint: PublicKey;
+    metadata: PublicKey;
+    edition: PublicKey;
+  };
+
+  // Creates the metadata of `mint`, as a verified member of `collection` if set.
+  const createMetadata = async (mint: PublicKey, collection?: Collection) => {
+    const metadata = metadataAddress(mint);
+    const tx = new Transaction().add(
+      createCreateMetadataAccountV3Instruction(
+        {
+          metadata,
+          mint,
+          mintAuthority: mintAuthority.publicKey,
+          payer: payer.publicKey,
+          updateAuthority: mintAuthority.publicKey,
+        },
+        {
+          createMetadataAccountArgsV3: {
+            data: {
+              name: "Lend Borrow",
+              symbol: "LB",
+              uri: "",
+              sellerFeeBasisPoints: 0,
+              creators: null,
+              collection: collection
+                ? { key: collection.mint, verified: false }
+                : null,
+              uses: null,
+            },
+            isMutable: true,
+            collectionDetails: null,
+          },
+        }
+      )
+    );
+
+    if (collection) {
+      tx.add(
+        createVerifyCollectionInstruction({
+          metadata,
+          collectionAuthority: mintAuthority.publicKey,
+          payer: payer.publicKey,
+          collectionMint: collection.mint,
+          collection: collection.metadata,
+          collectionMasterEditionAccount: collection.edition,
+        })
+      );
+    }
+
+    await provider.sendAndConfirm(tx, [payer, mintAuthority]);
+
+    return metadata;
+  };
+
+  it("Can borrow against a bundle of the collection's NFTs", async () => {
+    // a pool for a collection with verified members, unlike the placeholder above
+    const collectionNft = await mintNft(mintAuthority.publicKey);
+    const collection: Collection = {
+      mint: collectionNft.mint,
+      metadata: await createMetadata(collectionNft.mint),
+      edition: metadataAddress(collectionNft.mint, Buffer.from("edition")),
+    };
+
+    await provider.sendAndConfirm(
+      new Transaction().add(
+        createCreateMasterEditionV3Instruction(
+          {
+            edition: collection.edition,
+            mint: collection.mint,
+            updateAuthority: mintAuthority.publicKey,
+            mintAuthority: mintAuthority.publicKey,
+            payer: payer.publicKey,
+            metadata: collection.metadata,
+          },
+          { createMasterEditionArgs: { maxSupply: 0 } }
+        )
+      ),
+      [payer, mintAuthority]
+    );
+
+    [collectionPoolPDA] = PublicKey.findProgramAddressSync(
+      [
+        anchor.utils.bytes.utf8.encode("collection-pool"),
+        collection.mint.toBuffer(),
+      ],
+      program.programId
+    );
+    [vaultAuthorityPDA] = PublicKey.findProgramAddressSync(
+      [collectionPoolPDA.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .createPool(collection.mint, new anchor.BN(loanDuration))
+      .accounts({
+        collectionPool: collectionPoolPDA,
+        authority: assetPoolAuthority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([assetPoolAuthority])
+      .rpc();
+
+    // an NFT of the borrower with a vault token account waiting for it
+    const member = async (inCollection: boolean = true) => {
+      const nft = await mintNft(borrower.publicKey);
+      const metadata = await createMetadata(
+        nft.mint,
+        inCollection ? collection : undefined
+      );
+      const vault = await createAccount(
+        provider.connection,
+        payer,
+        nft.mint,
+        vaultAuthorityPDA,
+        anchor.web3.Keypair.generate(),
+        undefined,
+        TOKEN_PROGRAM_ID
+      );
+
+      return { ...nft, metadata, vault };
+    };
+    type Member = {
+      mint: PublicKey;
+      account: PublicKey;
+      metadata: PublicKey;
+      vault: PublicKey;
+    };
+
+    const flatten = (metas: anchor.web3.AccountMeta[][]) =>
+      metas.reduce((all, group) => all.concat(group), []);
+    const deposits = (nfts: Member[]) =>
+      flatten(
+        nfts.map((nft) => [
+          { pubkey: nft.account, isSigner: false, isWritable: true },
+          { pubkey: nft.mint, isSigner: false, isWritable: false },
+          { pubkey: nft.vault, isSigner: false, isWritable: true },
+          { pubkey: nft.metadata, isSigner: false, isWritable: false },
+        ])
+      );
+    const releases = (nfts: Member[], recipients: PublicKey[]) =>
+      flatten(
+        nfts.map((nft, i) => [
+          { pubkey: nft.vault, isSigner: false, isWritable: true },
+          { pubkey: recipients[i], isSigner: false, isWritable: true },
+        ])
+      );
+    const balance = async (account: PublicKey) =>
+      (await getAccount(provider.connection, account)).amount.toString();
+
+    const { offer, vault } = await openOffer(
+      new anchor.BN(LAMPORTS_PER_SOL),
+      3
+    );
+    const [first, second, third] = [
+      await member(),
+      await member(),
+      await member(),
+    ];
+    const foreign = await member(false);
+
+    // every NFT the offer asks for has to be posted, all from the collection
+    await expectError(
+      borrowAgainst(offer, vault, first, deposits([second])),
+      "CollateralMismatch"
+    );
+    await expectError(
+      borrowAgainst(offer, vault, first, deposits([second, foreign])),
+      "NotCollectionMember"
+    );
+
+    const loan = await borrowAgainst(
+      offer,
+      vault,
+      first,
+      deposits([second, third])
+    );
+
+    const bundled = await program.account.activeLoan.fetch(loan.activeLoan);
+
+    assert.deepEqual(
+      bundled.collateralMints.map((mint) => mint.toBase58()),
+      [first, second, third].map((nft) => nft.mint.toBase58())
+    );
+    assert.strictEqual(await balance(loan.vaultAsset), "1");
+
+    for (const nft of [second, third]) {
+      assert.strictEqual(await balance(nft.vault), "1");
+      assert.strictEqual(await balance(nft.account), "0");
+    }
+
+    // repaying hands every NFT of the bundle back to the borrower
+    await repayLoan(
+      offer,
+      vault,
+      first,
+      loan,
+      releases([second, third], [second.account, third.account])
+    );
+
+    for (const nft of [first, second, third]) {
+      assert.strictEqual(await balance(nft.account), "1");
+    }
+    for (const nft of [second, third]) {
+      assert.strictEqual(await balance(nft.vault), "0");
+    }
+
+    // and liquidating hands every NFT of the bundle to the lender
+    const next = await openOffer(new anchor.BN(LAMPORTS_PER_SOL), 3);
+    const [nextFirst, nextSecond, nextThird] = [
+      await member(),
+      await member(),
+      await member(),
+    ];
+    const nextLoan = await borrowAgainst(
+      next.offer,
+      next.vault,
+      nextFirst,
+      deposits([nextSecond, nextThird])
+    );
+
+    const lenderAccounts: PublicKey[] = [];
+    for (const nft of [nextFirst, nextSecond, nextThird]) {
+      lenderAccounts.push(
+        await createAccount(
+          provider.connection,
+          payer,
+          nft.mint,
+          lender.publicKey,
+          undefined,
+          undefined,
+          TOKEN_PROGRAM_ID
+        )
+      );
+    }
+
+    const { repayTs } = await program.account.activeLoan.fetch(
+      nextLoan.activeLoan
+    );
+    await sleep(repayTs.toNumber() * 1000 - Date.now() + 3000);
+
+    await program.methods
+      .liquidate()
+      .accounts({
+        activeLoan: nextLoan.activeLoan,
+        offer: next.offer,
+        collectionPool: collectionPoolPDA,
+        assetMint: nextFirst.mint,
+        vaultAssetAccount: nextLoan.vaultAsset,
+        lenderAssetAccount: lenderAccounts[0],
+        lender: lender.publicKey,
+        vaultAuthority: vaultAuthorityPDA,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      })
+      .remainingAccounts(
+        releases([nextSecond, nextThird], lenderAccounts.slice(1))
+      )
+      .signers([lender])
+      .rpc();
+
+    for (const account of lenderAccounts) {
+      assert.strictEqual(await balance(account), "1");
+    }
+    for (const nft of [nextSecond, nextThird]) {
+      assert.strictEqual(await balance(nft.vault), "0");
+    }
+    assert.strictEqual(await balance(nextLoan.vaultAsset), "0");
+  });
 });
 
 function sleep(ms: number) {
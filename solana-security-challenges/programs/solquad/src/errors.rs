@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("This project already exists in the pool.")]
+    ProjectExistsInPool,
+    #[msg("This project is not part of the pool.")]
+    ProjectNotInPool,
+    #[msg("Arithmetic overflow.")]
+    MathOverflow,
+    #[msg("Vote amount must be greater than zero.")]
+    InvalidAmount,
+    #[msg("The mint doesn't match the pool's vote mint.")]
+    InvalidVaultMint,
+    #[msg("The token account doesn't match the vault's mint or recipient.")]
+    InvalidTokenAccount,
+    #[msg("The token program is required for token vaults.")]
+    MissingTokenProgram,
+    #[msg("The project's match has already been paid.")]
+    MatchAlreadyPaid,
+    #[msg("Applications must close before voting starts, and voting before claims close.")]
+    InvalidRoundWindow,
+    #[msg("The application phase of the round is over.")]
+    ApplicationsClosed,
+    #[msg("The round is not in its voting phase.")]
+    NotInVotingPhase,
+    #[msg("Voting hasn't ended yet.")]
+    VotingNotEnded,
+    #[msg("The round is already finalized.")]
+    RoundFinalized,
+    #[msg("The round must be finalized first.")]
+    RoundNotFinalized,
+    #[msg("The project has already been tallied.")]
+    ProjectAlreadyTallied,
+    #[msg("Every project of the round must be tallied first.")]
+    TallyIncomplete,
+    #[msg("The escrow doesn't belong to the pool.")]
+    EscrowMismatch,
+    #[msg("The pool can't hold any more projects.")]
+    TooManyProjects,
+    #[msg("The project name is too long.")]
+    ProjectNameTooLong,
+    #[msg("The match cap must be between 1 and 10000 basis points.")]
+    InvalidMatchCap,
+    #[msg("Pairwise scores must come from the curator, one per project, and never above the quadratic score.")]
+    InvalidPairwiseScores,
+    #[msg("Token gated pools need a minimum balance greater than zero.")]
+    InvalidEligibility,
+    #[msg("The voter isn't eligible to vote in this pool.")]
+    VoterNotEligible,
+    #[msg("The project description or category is too long.")]
+    ProjectMetadataTooLong,
+    #[msg("Projects can't be updated once voting has started.")]
+    VotingStarted,
+    #[msg("The project has withdrawn from the pool.")]
+    ProjectWithdrawn,
+    #[msg("Refunds are only available for withdrawn projects and cancelled rounds.")]
+    RefundUnavailable,
+    #[msg("The round has been cancelled.")]
+    RoundCancelled,
+    #[msg("The round's results are tallied and can no longer be cancelled.")]
+    RoundTallied,
+    #[msg("The claim window of the round is closed.")]
+    ClaimWindowClosed,
+    #[msg("Unclaimed funds can only be swept after the claim deadline.")]
+    ClaimWindowOpen,
+    #[msg("Rounds must be opened in order of their id.")]
+    InvalidRoundId,
+    #[msg("Only the pool creator can cancel the round before its claim deadline.")]
+    CancelUnauthorized,
+}
@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct RegistryInitialized {
+    pub registry: Pubkey,
+    pub creator: Pubkey,
+}
+
+#[event]
+pub struct EscrowFunded {
+    pub escrow: Pubkey,
+    pub sponsor: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct PoolInitialized {
+    pub pool: Pubkey,
+    pub pool_creator: Pubkey,
+    pub escrow: Pubkey,
+    pub round_id: u64,
+}
+
+#[event]
+pub struct ProjectCreated {
+    pub pool: Pubkey,
+    pub project: Pubkey,
+    pub project_owner: Pubkey,
+}
+
+#[event]
+pub struct ProjectUpdated {
+    pub pool: Pubkey,
+    pub project: Pubkey,
+}
+
+#[event]
+pub struct ProjectAdded {
+    pub pool: Pubkey,
+    pub project: Pubkey,
+}
+
+#[event]
+pub struct VoteCast {
+    pub pool: Pubkey,
+    pub project: Pubkey,
+    pub voter: Pubkey,
+    pub amount: u64,
+    // everything the voter has contributed to the project so far
+    pub contribution: u64,
+}
+
+#[event]
+pub struct ProjectWithdrawn {
+    pub pool: Pubkey,
+    pub project: Pubkey,
+}
+
+#[event]
+pub struct RefundClaimed {
+    pub pool: Pubkey,
+    pub project: Pubkey,
+    pub voter: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ContributionsClaimed {
+    pub pool: Pubkey,
+    pub project: Pubkey,
+    pub payout: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct RoundFinalized {
+    pub pool: Pubkey,
+}
+
+#[event]
+pub struct ProjectTallied {
+    pub pool: Pubkey,
+    pub project: Pubkey,
+    pub score: u128,
+}
+
+#[event]
+pub struct RoundTallied {
+    pub pool: Pubkey,
+    pub total_score: u128,
+    pub matching_pool: u64,
+}
+
+#[event]
+pub struct MatchClaimed {
+    pub pool: Pubkey,
+    pub project: Pubkey,
+    pub payout: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct RoundCancelled {
+    pub pool: Pubkey,
+}
+
+#[event]
+pub struct SponsorRefunded {
+    pub pool: Pubkey,
+    pub sponsor: Pubkey,
+    pub amount: u64,
+}
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::states::*;
+
+#[derive(Accounts)]
+pub struct AddProjectToPool<'info> {
+    #[account(
+        mut,
+        address = pool_account.escrow @ ErrorCode::EscrowMismatch,
+        realloc = Escrow::space(escrow_account.project_reciever_addresses.len() + 1),
+        realloc::payer = pool_creator,
+        realloc::zero = false,
+    )]
+    pub escrow_account: Account<'info, Escrow>,
+    #[account(
+        mut,
+        has_one = pool_creator,
+        realloc = Pool::space(pool_account.projects.len() + 1),
+        realloc::payer = pool_creator,
+        realloc::zero = false,
+        seeds = [
+            b"pool".as_ref(),
+            pool_creator.key().as_ref(),
+            pool_account.round_id.to_le_bytes().as_ref(),
+        ],
+        bump = pool_account.bump,
+    )]
+    pub pool_account: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [
+            b"project".as_ref(),
+            pool_account.key().as_ref(),
+            project_account.project_owner.as_ref(),
+        ],
+        bump = project_account.bump,
+    )]
+    pub project_account: Account<'info, Project>,
+    // the pool's curator approves every project joining the round
+    #[account(mut)]
+    pub pool_creator: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AddProjectToPool>) -> Result<()> {
+    let escrow_account = &mut ctx.accounts.escrow_account;
+    let pool_account = &mut ctx.accounts.pool_account;
+    let project_account = &mut ctx.accounts.project_account;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(
+        pool_account.accepts_projects(now),
+        ErrorCode::ApplicationsClosed
+    );
+    require!(
+        !project_account.in_pool,
+        ErrorCode::ProjectExistsInPool
+    );
+    require!(!project_account.withdrawn, ErrorCode::ProjectWithdrawn);
+    require!(
+        pool_account.projects.len() < Pool::MAX_PROJECTS,
+        ErrorCode::TooManyProjects
+    );
+    require!(
+        !pool_account
+            .projects
+            .iter()
+            .any(|owner| owner == &project_account.project_owner),
+        ErrorCode::ProjectExistsInPool
+    );
+
+    pool_account.projects.push(project_account.project_owner);
+    pool_account.total_projects += 1;
+    project_account.in_pool = true;
+
+    escrow_account
+        .project_reciever_addresses
+        .push(project_account.project_owner);
+
+    emit!(ProjectAdded {
+        pool: pool_account.key(),
+        project: project_account.key(),
+    });
+
+    Ok(())
+}
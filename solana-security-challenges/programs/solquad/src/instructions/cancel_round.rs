@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::states::*;
+
+#[derive(Accounts)]
+pub struct CancelRound<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"pool".as_ref(),
+            pool_account.pool_creator.as_ref(),
+            pool_account.round_id.to_le_bytes().as_ref(),
+        ],
+        bump = pool_account.bump,
+    )]
+    pub pool_account: Account<'info, Pool>,
+    // the pool creator, or anyone once the claim deadline passed without a tally
+    pub canceller: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CancelRound>) -> Result<()> {
+    let pool_account = &mut ctx.accounts.pool_account;
+
+    require!(!pool_account.cancelled, ErrorCode::RoundCancelled);
+    require!(!pool_account.is_tallied(), ErrorCode::RoundTallied);
+    // an abandoned round would otherwise lock the votes and the escrow for good
+    require!(
+        ctx.accounts.canceller.key() == pool_account.pool_creator
+            || Clock::get()?.unix_timestamp >= pool_account.claim_deadline,
+        ErrorCode::CancelUnauthorized
+    );
+
+    pool_account.cancelled = true;
+
+    emit!(RoundCancelled {
+        pool: pool_account.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::states::*;
+use crate::vault::Vault;
+
+#[derive(Accounts)]
+pub struct ClaimContributions<'info> {
+    #[account(
+        seeds = [
+            b"pool".as_ref(),
+            pool_account.pool_creator.as_ref(),
+            pool_account.round_id.to_le_bytes().as_ref(),
+        ],
+        bump = pool_account.bump,
+    )]
+    pub pool_account: Account<'info, Pool>,
+    #[account(
+        has_one = project_owner,
+        seeds = [b"project".as_ref(), pool_account.key().as_ref(), project_owner.key().as_ref()],
+        bump = project_account.bump,
+    )]
+    pub project_account: Account<'info, Project>,
+    /// CHECK: vault created alongside the project
+    #[account(
+        mut,
+        seeds = [b"project-vault".as_ref(), project_account.key().as_ref()],
+        bump = project_account.vault_bump,
+    )]
+    pub project_vault: UncheckedAccount<'info>,
+    pub project_owner: Signer<'info>,
+    /// CHECK: receives the contributions, checked against the project
+    #[account(mut, address = project_account.payout)]
+    pub payout: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payout_token_account: Option<Account<'info, TokenAccount>>,
+    pub token_program: Option<Program<'info, Token>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ClaimContributions>) -> Result<()> {
+    // contributions stay refundable until the round's results are final
+    require!(
+        ctx.accounts.pool_account.is_tallied(),
+        ErrorCode::TallyIncomplete
+    );
+    require!(
+        !ctx.accounts.project_account.withdrawn,
+        ErrorCode::ProjectWithdrawn
+    );
+
+    let project_account = &ctx.accounts.project_account;
+    let project_key = project_account.key();
+    let vault_seeds: &[&[u8]] = &[
+        b"project-vault".as_ref(),
+        project_key.as_ref(),
+        &[project_account.vault_bump],
+    ];
+
+    let vault = Vault {
+        account: &ctx.accounts.project_vault.to_account_info(),
+        mint: ctx.accounts.pool_account.vote_mint,
+        token_program: ctx.accounts.token_program.as_ref().map(|p| p.as_ref()),
+        system_program: &ctx.accounts.system_program.to_account_info(),
+    };
+
+    let amount = vault.balance()?;
+    vault.withdraw(
+        vault_seeds,
+        &ctx.accounts.payout.to_account_info(),
+        ctx.accounts.payout_token_account.as_ref(),
+        amount,
+    )?;
+
+    emit!(ContributionsClaimed {
+        pool: ctx.accounts.pool_account.key(),
+        project: project_key,
+        payout: ctx.accounts.payout.key(),
+        amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::qf;
+use crate::states::*;
+use crate::vault::Vault;
+
+#[derive(Accounts)]
+pub struct ClaimMatch<'info> {
+    #[account(address = pool_account.escrow @ ErrorCode::EscrowMismatch)]
+    pub escrow_account: Account<'info, Escrow>,
+    /// CHECK: vault created alongside the escrow
+    #[account(
+        mut,
+        seeds = [b"escrow-vault".as_ref(), escrow_account.key().as_ref()],
+        bump = escrow_account.vault_bump,
+    )]
+    pub escrow_vault: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            b"pool".as_ref(),
+            pool_account.pool_creator.as_ref(),
+            pool_account.round_id.to_le_bytes().as_ref(),
+        ],
+        bump = pool_account.bump,
+    )]
+    pub pool_account: Account<'info, Pool>,
+    #[account(
+        mut,
+        has_one = project_owner,
+        seeds = [b"project".as_ref(), pool_account.key().as_ref(), project_owner.key().as_ref()],
+        bump = project_account.bump,
+    )]
+    pub project_account: Account<'info, Project>,
+    pub project_owner: Signer<'info>,
+    /// CHECK: receives the match, checked against the project
+    #[account(mut, address = project_account.payout)]
+    pub payout: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payout_token_account: Option<Account<'info, TokenAccount>>,
+    pub token_program: Option<Program<'info, Token>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ClaimMatch>) -> Result<()> {
+    let escrow_account = &ctx.accounts.escrow_account;
+    let pool_account = &ctx.accounts.pool_account;
+    let project_account = &mut ctx.accounts.project_account;
+
+    require!(pool_account.is_tallied(), ErrorCode::TallyIncomplete);
+    require!(!project_account.match_paid, ErrorCode::MatchAlreadyPaid);
+    require!(
+        pool_account.refund_pool.is_none()
+            && Clock::get()?.unix_timestamp < pool_account.claim_deadline,
+        ErrorCode::ClaimWindowClosed
+    );
+
+    project_account.distributed_amt = qf::capped_match(
+        pool_account.matching_pool,
+        project_account.score,
+        pool_account.total_score,
+        pool_account.max_match_bps,
+    )
+    .ok_or(ErrorCode::MathOverflow)?;
+    project_account.match_paid = true;
+
+    let escrow_key = escrow_account.key();
+    let vault_seeds: &[&[u8]] = &[
+        b"escrow-vault".as_ref(),
+        escrow_key.as_ref(),
+        &[escrow_account.vault_bump],
+    ];
+
+    Vault {
+        account: &ctx.accounts.escrow_vault.to_account_info(),
+        mint: escrow_account.mint,
+        token_program: ctx.accounts.token_program.as_ref().map(|p| p.as_ref()),
+        system_program: &ctx.accounts.system_program.to_account_info(),
+    }
+    .withdraw(
+        vault_seeds,
+        &ctx.accounts.payout.to_account_info(),
+        ctx.accounts.payout_token_account.as_ref(),
+        project_account.distributed_amt,
+    )?;
+
+    emit!(MatchClaimed {
+        pool: pool_account.key(),
+        project: project_account.key(),
+        payout: ctx.accounts.payout.key(),
+        amount: project_account.distributed_amt,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::qf;
+use crate::states::*;
+use crate::vault::Vault;
+
+#[derive(Accounts)]
+pub struct ClaimRefund<'info> {
+    #[account(
+        seeds = [
+            b"pool".as_ref(),
+            pool_account.pool_creator.as_ref(),
+            pool_account.round_id.to_le_bytes().as_ref(),
+        ],
+        bump = pool_account.bump,
+    )]
+    pub pool_account: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [
+            b"project".as_ref(),
+            pool_account.key().as_ref(),
+            project_account.project_owner.as_ref(),
+        ],
+        bump = project_account.bump,
+    )]
+    pub project_account: Account<'info, Project>,
+    /// CHECK: vault created alongside the project
+    #[account(
+        mut,
+        seeds = [b"project-vault".as_ref(), project_account.key().as_ref()],
+        bump = project_account.vault_bump,
+    )]
+    pub project_vault: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        close = voter,
+        has_one = voter,
+        seeds = [
+            b"voter".as_ref(),
+            pool_account.key().as_ref(),
+            project_account.key().as_ref(),
+            voter.key().as_ref(),
+        ],
+        bump = voter_account.bump,
+    )]
+    pub voter_account: Account<'info, Voter>,
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    #[account(mut)]
+    pub voter_token_account: Option<Account<'info, TokenAccount>>,
+    pub token_program: Option<Program<'info, Token>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ClaimRefund>) -> Result<()> {
+    let project_account = &mut ctx.accounts.project_account;
+    let voter_account = &ctx.accounts.voter_account;
+
+    require!(
+        project_account.withdrawn || ctx.accounts.pool_account.cancelled,
+        ErrorCode::RefundUnavailable
+    );
+
+    let refund = voter_account.token_amount;
+
+    project_account.sum_sqrt_contributions = project_account
+        .sum_sqrt_contributions
+        .saturating_sub(qf::sqrt_contribution(refund));
+    project_account.voter_amount = project_account.voter_amount.saturating_sub(refund);
+    project_account.votes_count = project_account.votes_count.saturating_sub(1);
+
+    let project_key = project_account.key();
+    let vault_seeds: &[&[u8]] = &[
+        b"project-vault".as_ref(),
+        project_key.as_ref(),
+        &[project_account.vault_bump],
+    ];
+
+    Vault {
+        account: &ctx.accounts.project_vault.to_account_info(),
+        mint: ctx.accounts.pool_account.vote_mint,
+        token_program: ctx.accounts.token_program.as_ref().map(|p| p.as_ref()),
+        system_program: &ctx.accounts.system_program.to_account_info(),
+    }
+    .withdraw(
+        vault_seeds,
+        &ctx.accounts.voter.to_account_info(),
+        ctx.accounts.voter_token_account.as_ref(),
+        refund,
+    )?;
+
+    emit!(RefundClaimed {
+        pool: ctx.accounts.pool_account.key(),
+        project: project_key,
+        voter: ctx.accounts.voter.key(),
+        amount: refund,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::qf;
+use crate::states::*;
+use crate::vault::Vault;
+
+#[derive(Accounts)]
+pub struct RefundSponsor<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"pool".as_ref(),
+            pool_account.pool_creator.as_ref(),
+            pool_account.round_id.to_le_bytes().as_ref(),
+        ],
+        bump = pool_account.bump,
+    )]
+    pub pool_account: Account<'info, Pool>,
+    #[account(address = pool_account.escrow @ ErrorCode::EscrowMismatch)]
+    pub escrow_account: Account<'info, Escrow>,
+    /// CHECK: vault created alongside the escrow
+    #[account(
+        mut,
+        seeds = [b"escrow-vault".as_ref(), escrow_account.key().as_ref()],
+        bump = escrow_account.vault_bump,
+    )]
+    pub escrow_vault: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        close = sponsor,
+        has_one = sponsor,
+        seeds = [
+            b"sponsor".as_ref(),
+            escrow_account.key().as_ref(),
+            sponsor.key().as_ref(),
+        ],
+        bump = sponsor_account.bump,
+    )]
+    pub sponsor_account: Account<'info, Sponsor>,
+    /// CHECK: receives the refund, checked against the sponsor record
+    #[account(mut)]
+    pub sponsor: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub sponsor_token_account: Option<Account<'info, TokenAccount>>,
+    pub token_program: Option<Program<'info, Token>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RefundSponsor>) -> Result<()> {
+    require!(
+        ctx.accounts.pool_account.cancelled,
+        ErrorCode::RefundUnavailable
+    );
+
+    refund_sponsor(ctx.accounts)
+}
+
+// Pays a sponsor their share of what is left in the escrow, pro-rata to what they deposited.
+// The leftover is snapshotted on the first refund so every sponsor is paid from the same amount,
+// and the last sponsor refunded empties the vault.
+pub(crate) fn refund_sponsor(accounts: &mut RefundSponsor) -> Result<()> {
+    let escrow_account = &accounts.escrow_account;
+    let pool_account = &mut accounts.pool_account;
+
+    let escrow_key = escrow_account.key();
+    let vault_seeds: &[&[u8]] = &[
+        b"escrow-vault".as_ref(),
+        escrow_key.as_ref(),
+        &[escrow_account.vault_bump],
+    ];
+
+    let vault = Vault {
+        account: &accounts.escrow_vault.to_account_info(),
+        mint: escrow_account.mint,
+        token_program: accounts.token_program.as_ref().map(|p| p.as_ref()),
+        system_program: &accounts.system_program.to_account_info(),
+    };
+
+    let refund_pool = match pool_account.refund_pool {
+        Some(refund_pool) => refund_pool,
+        None => {
+            let leftover = vault.balance()?;
+            pool_account.refund_pool = Some(leftover);
+            leftover
+        }
+    };
+
+    let refunded_deposits = pool_account
+        .refunded_deposits
+        .checked_add(accounts.sponsor_account.amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    // the last sponsor also gets the rounding dust left by the earlier shares
+    let refund = if refunded_deposits == escrow_account.total_deposits {
+        vault.balance()?
+    } else {
+        qf::mul_div(
+            refund_pool as u128,
+            accounts.sponsor_account.amount as u128,
+            escrow_account.total_deposits as u128,
+        )
+        .and_then(|refund| u64::try_from(refund).ok())
+        .ok_or(ErrorCode::MathOverflow)?
+    };
+    pool_account.refunded_deposits = refunded_deposits;
+
+    vault.withdraw(
+        vault_seeds,
+        &accounts.sponsor.to_account_info(),
+        accounts.sponsor_token_account.as_ref(),
+        refund,
+    )?;
+
+    emit!(SponsorRefunded {
+        pool: pool_account.key(),
+        sponsor: accounts.sponsor.key(),
+        amount: refund,
+    });
+
+    Ok(())
+}
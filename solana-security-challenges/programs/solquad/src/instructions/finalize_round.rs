@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::states::*;
+
+#[derive(Accounts)]
+pub struct FinalizeRound<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"pool".as_ref(),
+            pool_account.pool_creator.as_ref(),
+            pool_account.round_id.to_le_bytes().as_ref(),
+        ],
+        bump = pool_account.bump,
+    )]
+    pub pool_account: Account<'info, Pool>,
+}
+
+pub fn handler(ctx: Context<FinalizeRound>) -> Result<()> {
+    let pool_account = &mut ctx.accounts.pool_account;
+
+    require!(!pool_account.cancelled, ErrorCode::RoundCancelled);
+    require!(!pool_account.finalized, ErrorCode::RoundFinalized);
+    require!(
+        Clock::get()?.unix_timestamp >= pool_account.voting_end,
+        ErrorCode::VotingNotEnded
+    );
+
+    pool_account.finalized = true;
+
+    emit!(RoundFinalized {
+        pool: pool_account.key(),
+    });
+
+    Ok(())
+}
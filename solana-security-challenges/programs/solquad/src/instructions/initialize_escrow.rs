@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::events::*;
+use crate::states::*;
+use crate::vault::Vault;
+
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct InitializeEscrow<'info> {
+    #[account(
+        init,
+        payer = escrow_signer,
+        space = Escrow::space(0),
+        seeds = [
+            b"escrow".as_ref(),
+            escrow_signer.key().as_ref(),
+            round_id.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub escrow_account: Account<'info, Escrow>,
+    /// CHECK: created by the instruction as a lamport or token vault of `mint`
+    #[account(
+        mut,
+        seeds = [b"escrow-vault".as_ref(), escrow_account.key().as_ref()],
+        bump,
+    )]
+    pub escrow_vault: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = escrow_signer,
+        space = Sponsor::LEN,
+        seeds = [
+            b"sponsor".as_ref(),
+            escrow_account.key().as_ref(),
+            escrow_signer.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub sponsor_account: Account<'info, Sponsor>,
+    #[account(mut)]
+    pub escrow_signer: Signer<'info>,
+    // matching funds are held in lamports when no mint is given
+    pub mint: Option<Account<'info, Mint>>,
+    #[account(mut)]
+    pub signer_token_account: Option<Account<'info, TokenAccount>>,
+    pub token_program: Option<Program<'info, Token>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeEscrow>, round_id: u64, amount: u64) -> Result<()> {
+    let escrow_account = &mut ctx.accounts.escrow_account;
+    escrow_account.escrow_creator = ctx.accounts.escrow_signer.key();
+    escrow_account.round_id = round_id;
+    escrow_account.pool = Pubkey::default();
+    escrow_account.creator_deposit_amount = amount;
+    escrow_account.total_deposits = amount;
+    escrow_account.total_projects = 0;
+    escrow_account.mint = ctx.accounts.mint.as_ref().map(|mint| mint.key());
+    escrow_account.vault_bump = ctx.bumps.escrow_vault;
+    escrow_account.bump = ctx.bumps.escrow_account;
+
+    let sponsor_account = &mut ctx.accounts.sponsor_account;
+    sponsor_account.sponsor = ctx.accounts.escrow_signer.key();
+    sponsor_account.escrow = escrow_account.key();
+    sponsor_account.amount = amount;
+    sponsor_account.bump = ctx.bumps.sponsor_account;
+
+    let escrow_key = escrow_account.key();
+    let vault_seeds: &[&[u8]] = &[
+        b"escrow-vault".as_ref(),
+        escrow_key.as_ref(),
+        &[escrow_account.vault_bump],
+    ];
+
+    let vault = Vault {
+        account: &ctx.accounts.escrow_vault.to_account_info(),
+        mint: escrow_account.mint,
+        token_program: ctx.accounts.token_program.as_ref().map(|p| p.as_ref()),
+        system_program: &ctx.accounts.system_program.to_account_info(),
+    };
+
+    vault.create(
+        vault_seeds,
+        ctx.accounts.mint.as_ref().map(|mint| mint.as_ref()),
+        &ctx.accounts.escrow_signer.to_account_info(),
+    )?;
+    vault.deposit(
+        &ctx.accounts.escrow_signer.to_account_info(),
+        ctx.accounts.signer_token_account.as_ref(),
+        amount,
+    )?;
+
+    emit!(EscrowFunded {
+        escrow: escrow_key,
+        sponsor: ctx.accounts.escrow_signer.key(),
+        amount,
+    });
+
+    Ok(())
+}
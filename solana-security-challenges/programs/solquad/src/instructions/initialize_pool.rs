@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::qf;
+use crate::states::*;
+
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct InitializePool<'info> {
+    #[account(
+        init,
+        payer = pool_signer,
+        space = Pool::space(0),
+        seeds = [
+            b"pool".as_ref(),
+            pool_signer.key().as_ref(),
+            round_id.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub pool_account: Account<'info, Pool>,
+    // the escrow of the same round funds the pool
+    #[account(
+        mut,
+        seeds = [
+            b"escrow".as_ref(),
+            pool_signer.key().as_ref(),
+            round_id.to_le_bytes().as_ref(),
+        ],
+        bump = escrow_account.bump,
+    )]
+    pub escrow_account: Account<'info, Escrow>,
+    #[account(
+        mut,
+        realloc = RoundRegistry::space(registry.rounds.len() + 1),
+        realloc::payer = pool_signer,
+        realloc::zero = false,
+        seeds = [b"registry".as_ref(), pool_signer.key().as_ref()],
+        bump = registry.bump,
+    )]
+    pub registry: Account<'info, RoundRegistry>,
+    #[account(mut)]
+    pub pool_signer: Signer<'info>,
+    // votes are cast in lamports when no mint is given
+    pub vote_mint: Option<Account<'info, Mint>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handler(
+    ctx: Context<InitializePool>,
+    round_id: u64,
+    application_end: i64,
+    voting_start: i64,
+    voting_end: i64,
+    max_match_bps: Option<u16>,
+    matching_mode: MatchingMode,
+    eligibility: Eligibility,
+    claim_deadline: i64,
+) -> Result<()> {
+    // rounds of a creator are numbered in the order they are opened
+    require!(
+        round_id == ctx.accounts.registry.rounds.len() as u64,
+        ErrorCode::InvalidRoundId
+    );
+    require!(
+        application_end <= voting_start && voting_start < voting_end && voting_end < claim_deadline,
+        ErrorCode::InvalidRoundWindow
+    );
+    if let Some(bps) = max_match_bps {
+        require!(
+            bps > 0 && bps as u128 <= qf::BPS_DENOMINATOR,
+            ErrorCode::InvalidMatchCap
+        );
+    }
+    if let Eligibility::TokenGate { min_balance, .. } = eligibility {
+        require!(min_balance > 0, ErrorCode::InvalidEligibility);
+    }
+
+    let pool_account = &mut ctx.accounts.pool_account;
+    pool_account.pool_creator = ctx.accounts.pool_signer.key();
+    pool_account.escrow = ctx.accounts.escrow_account.key();
+    pool_account.bump = ctx.bumps.pool_account;
+    pool_account.total_projects = 0;
+    pool_account.total_votes = 0;
+    pool_account.total_score = 0;
+    pool_account.tallied_projects = 0;
+    pool_account.matching_pool = 0;
+    pool_account.vote_mint = ctx.accounts.vote_mint.as_ref().map(|mint| mint.key());
+    pool_account.application_end = application_end;
+    pool_account.voting_start = voting_start;
+    pool_account.voting_end = voting_end;
+    pool_account.finalized = false;
+    pool_account.max_match_bps = max_match_bps;
+    pool_account.matching_mode = matching_mode;
+    pool_account.eligibility = eligibility;
+    pool_account.claim_deadline = claim_deadline;
+    pool_account.cancelled = false;
+    pool_account.refund_pool = None;
+    pool_account.refunded_deposits = 0;
+    pool_account.round_id = round_id;
+
+    ctx.accounts.escrow_account.pool = pool_account.key();
+    ctx.accounts.registry.rounds.push(pool_account.key());
+
+    emit!(PoolInitialized {
+        pool: pool_account.key(),
+        pool_creator: pool_account.pool_creator,
+        escrow: pool_account.escrow,
+        round_id,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token};
+
+use crate::events::*;
+use crate::states::*;
+use crate::vault::Vault;
+
+#[derive(Accounts)]
+pub struct InitializeProject<'info> {
+    #[account(
+        init,
+        payer = project_owner,
+        space = Project::LEN,
+        seeds = [b"project".as_ref(), pool_account.key().as_ref(), project_owner.key().as_ref()],
+        bump,
+    )]
+    pub project_account: Account<'info, Project>,
+    /// CHECK: created by the instruction as a lamport or token vault of the pool's vote mint
+    #[account(
+        mut,
+        seeds = [b"project-vault".as_ref(), project_account.key().as_ref()],
+        bump,
+    )]
+    pub project_vault: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub project_owner: Signer<'info>,
+    #[account(
+        seeds = [
+            b"pool".as_ref(),
+            pool_account.pool_creator.as_ref(),
+            pool_account.round_id.to_le_bytes().as_ref(),
+        ],
+        bump = pool_account.bump,
+    )]
+    pub pool_account: Account<'info, Pool>,
+    pub vote_mint: Option<Account<'info, Mint>>,
+    pub token_program: Option<Program<'info, Token>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<InitializeProject>,
+    name: String,
+    description_uri: String,
+    category: String,
+    payout: Pubkey,
+) -> Result<()> {
+    Project::validate_metadata(&name, &description_uri, &category)?;
+
+    let project_account = &mut ctx.accounts.project_account;
+
+    project_account.project_owner = ctx.accounts.project_owner.key();
+    project_account.project_name = name;
+    project_account.description_uri = description_uri;
+    project_account.category = category;
+    project_account.payout = payout;
+    project_account.withdrawn = false;
+    project_account.votes_count = 0;
+    project_account.voter_amount = 0;
+    project_account.distributed_amt = 0;
+    project_account.in_pool = false;
+    project_account.sum_sqrt_contributions = 0;
+    project_account.vault_bump = ctx.bumps.project_vault;
+    project_account.match_paid = false;
+    project_account.tallied = false;
+    project_account.bump = ctx.bumps.project_account;
+    project_account.score = 0;
+
+    let project_key = project_account.key();
+    let vault_seeds: &[&[u8]] = &[
+        b"project-vault".as_ref(),
+        project_key.as_ref(),
+        &[project_account.vault_bump],
+    ];
+
+    Vault {
+        account: &ctx.accounts.project_vault.to_account_info(),
+        mint: ctx.accounts.pool_account.vote_mint,
+        token_program: ctx.accounts.token_program.as_ref().map(|p| p.as_ref()),
+        system_program: &ctx.accounts.system_program.to_account_info(),
+    }
+    .create(
+        vault_seeds,
+        ctx.accounts.vote_mint.as_ref().map(|mint| mint.as_ref()),
+        &ctx.accounts.project_owner.to_account_info(),
+    )?;
+
+    emit!(ProjectCreated {
+        pool: ctx.accounts.pool_account.key(),
+        project: project_key,
+        project_owner: ctx.accounts.project_owner.key(),
+    });
+
+    Ok(())
+}
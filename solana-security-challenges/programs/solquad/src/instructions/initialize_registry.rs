@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::events::*;
+use crate::states::*;
+
+#[derive(Accounts)]
+pub struct InitializeRegistry<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = RoundRegistry::space(0),
+        seeds = [b"registry".as_ref(), creator.key().as_ref()],
+        bump,
+    )]
+    pub registry: Account<'info, RoundRegistry>,
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeRegistry>) -> Result<()> {
+    let registry = &mut ctx.accounts.registry;
+    registry.creator = ctx.accounts.creator.key();
+    registry.rounds = vec![];
+    registry.bump = ctx.bumps.registry;
+
+    emit!(RegistryInitialized {
+        registry: registry.key(),
+        creator: registry.creator,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+pub mod add_project_to_pool;
+pub mod cancel_round;
+pub mod claim_contributions;
+pub mod claim_match;
+pub mod claim_refund;
+pub mod claim_sponsor_refund;
+pub mod finalize_round;
+pub mod initialize_escrow;
+pub mod initialize_pool;
+pub mod initialize_project;
+pub mod initialize_registry;
+pub mod sweep_unclaimed;
+pub mod tally_round;
+pub mod top_up_escrow;
+pub mod update_project;
+pub mod vote_for_project;
+pub mod withdraw_from_pool;
+
+pub use add_project_to_pool::*;
+pub use cancel_round::*;
+pub use claim_contributions::*;
+pub use claim_match::*;
+pub use claim_refund::*;
+pub use claim_sponsor_refund::*;
+pub use finalize_round::*;
+pub use initialize_escrow::*;
+pub use initialize_pool::*;
+pub use initialize_project::*;
+pub use initialize_registry::*;
+pub use tally_round::*;
+pub use top_up_escrow::*;
+pub use update_project::*;
+pub use vote_for_project::*;
+pub use withdraw_from_pool::*;
@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+use super::claim_sponsor_refund::{refund_sponsor, RefundSponsor};
+use crate::errors::ErrorCode;
+
+pub fn handler(ctx: Context<RefundSponsor>) -> Result<()> {
+    require!(
+        Clock::get()?.unix_timestamp >= ctx.accounts.pool_account.claim_deadline,
+        ErrorCode::ClaimWindowOpen
+    );
+
+    refund_sponsor(ctx.accounts)
+}
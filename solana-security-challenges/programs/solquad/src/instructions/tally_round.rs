@@ -0,0 +1,134 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::qf;
+use crate::states::*;
+use crate::vault::Vault;
+
+#[derive(Accounts)]
+pub struct TallyRound<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"pool".as_ref(),
+            pool_account.pool_creator.as_ref(),
+            pool_account.round_id.to_le_bytes().as_ref(),
+        ],
+        bump = pool_account.bump,
+    )]
+    pub pool_account: Account<'info, Pool>,
+    #[account(address = pool_account.escrow @ ErrorCode::EscrowMismatch)]
+    pub escrow_account: Account<'info, Escrow>,
+    /// CHECK: vault created alongside the escrow
+    #[account(
+        seeds = [b"escrow-vault".as_ref(), escrow_account.key().as_ref()],
+        bump = escrow_account.vault_bump,
+    )]
+    pub escrow_vault: UncheckedAccount<'info>,
+    pub cranker: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, TallyRound<'info>>,
+    pairwise_scores: Vec<u128>,
+) -> Result<()> {
+    let pool_account = &mut ctx.accounts.pool_account;
+    let pool_key = pool_account.key();
+
+    require!(pool_account.finalized, ErrorCode::RoundNotFinalized);
+    require!(!pool_account.cancelled, ErrorCode::RoundCancelled);
+    require!(
+        pool_account.refund_pool.is_none(),
+        ErrorCode::ClaimWindowClosed
+    );
+
+    // pairwise scores are submitted by the pool creator, one per project tallied
+    match pool_account.matching_mode {
+        MatchingMode::Quadratic => {
+            require!(pairwise_scores.is_empty(), ErrorCode::InvalidPairwiseScores)
+        }
+        MatchingMode::Pairwise => {
+            require_keys_eq!(
+                ctx.accounts.cranker.key(),
+                pool_account.pool_creator,
+                ErrorCode::InvalidPairwiseScores
+            );
+            require!(
+                pairwise_scores.len() == ctx.remaining_accounts.len(),
+                ErrorCode::InvalidPairwiseScores
+            );
+        }
+    }
+
+    for (index, info) in ctx.remaining_accounts.iter().enumerate() {
+        let mut project_account = Account::<Project>::try_from(info)?;
+
+        let expected = Pubkey::create_program_address(
+            &[
+                b"project".as_ref(),
+                pool_key.as_ref(),
+                project_account.project_owner.as_ref(),
+                &[project_account.bump],
+            ],
+            ctx.program_id,
+        )
+        .map_err(|_| ErrorCode::ProjectNotInPool)?;
+        require_keys_eq!(info.key(), expected, ErrorCode::ProjectNotInPool);
+        require!(project_account.in_pool, ErrorCode::ProjectNotInPool);
+        require!(!project_account.tallied, ErrorCode::ProjectAlreadyTallied);
+
+        // only the subsidy on top of what voters paid themselves is matched
+        let mut project_score = qf::score(project_account.sum_sqrt_contributions)
+            .and_then(|score| qf::subsidy(score, project_account.voter_amount))
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        // pairwise matching only ever damps the plain quadratic funding subsidy
+        if let Some(pairwise_score) = pairwise_scores.get(index) {
+            require!(
+                *pairwise_score <= project_score,
+                ErrorCode::InvalidPairwiseScores
+            );
+            project_score = *pairwise_score;
+        }
+
+        pool_account.total_score = pool_account
+            .total_score
+            .checked_add(project_score)
+            .ok_or(ErrorCode::MathOverflow)?;
+        pool_account.tallied_projects = pool_account
+            .tallied_projects
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        project_account.score = project_score;
+        project_account.tallied = true;
+        project_account.exit(ctx.program_id)?;
+
+        emit!(ProjectTallied {
+            pool: pool_key,
+            project: info.key(),
+            score: project_score,
+        });
+    }
+
+    // matches are shared out of the escrow balance when the last project is tallied
+    if pool_account.is_tallied() {
+        pool_account.matching_pool = Vault {
+            account: &ctx.accounts.escrow_vault.to_account_info(),
+            mint: ctx.accounts.escrow_account.mint,
+            token_program: None,
+            system_program: &ctx.accounts.system_program.to_account_info(),
+        }
+        .balance()?;
+
+        emit!(RoundTallied {
+            pool: pool_key,
+            total_score: pool_account.total_score,
+            matching_pool: pool_account.matching_pool,
+        });
+    }
+
+    Ok(())
+}
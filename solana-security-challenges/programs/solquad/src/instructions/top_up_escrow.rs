@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::states::*;
+use crate::vault::Vault;
+
+#[derive(Accounts)]
+pub struct TopUpEscrow<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"escrow".as_ref(),
+            escrow_account.escrow_creator.as_ref(),
+            escrow_account.round_id.to_le_bytes().as_ref(),
+        ],
+        bump = escrow_account.bump,
+    )]
+    pub escrow_account: Account<'info, Escrow>,
+    #[account(address = escrow_account.pool @ ErrorCode::EscrowMismatch)]
+    pub pool_account: Account<'info, Pool>,
+    /// CHECK: vault created alongside the escrow
+    #[account(
+        mut,
+        seeds = [b"escrow-vault".as_ref(), escrow_account.key().as_ref()],
+        bump = escrow_account.vault_bump,
+    )]
+    pub escrow_vault: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = sponsor,
+        space = Sponsor::LEN,
+        seeds = [
+            b"sponsor".as_ref(),
+            escrow_account.key().as_ref(),
+            sponsor.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub sponsor_account: Account<'info, Sponsor>,
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+    #[account(mut)]
+    pub sponsor_token_account: Option<Account<'info, TokenAccount>>,
+    pub token_program: Option<Program<'info, Token>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<TopUpEscrow>, amount: u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    // matches and sponsor refunds are shared out of a snapshot of the escrow,
+    // deposits arriving after it would be stranded
+    let pool_account = &ctx.accounts.pool_account;
+    require!(!pool_account.cancelled, ErrorCode::RoundCancelled);
+    require!(!pool_account.is_tallied(), ErrorCode::RoundTallied);
+    require!(
+        pool_account.refund_pool.is_none(),
+        ErrorCode::ClaimWindowClosed
+    );
+
+    let escrow_account = &mut ctx.accounts.escrow_account;
+    let sponsor_account = &mut ctx.accounts.sponsor_account;
+
+    if sponsor_account.sponsor == Pubkey::default() {
+        sponsor_account.sponsor = ctx.accounts.sponsor.key();
+        sponsor_account.escrow = escrow_account.key();
+        sponsor_account.amount = 0;
+        sponsor_account.bump = ctx.bumps.sponsor_account;
+    }
+
+    Vault {
+        account: &ctx.accounts.escrow_vault.to_account_info(),
+        mint: escrow_account.mint,
+        token_program: ctx.accounts.token_program.as_ref().map(|p| p.as_ref()),
+        system_program: &ctx.accounts.system_program.to_account_info(),
+    }
+    .deposit(
+        &ctx.accounts.sponsor.to_account_info(),
+        ctx.accounts.sponsor_token_account.as_ref(),
+        amount,
+    )?;
+
+    sponsor_account.amount = sponsor_account
+        .amount
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    escrow_account.total_deposits = escrow_account
+        .total_deposits
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    emit!(EscrowFunded {
+        escrow: escrow_account.key(),
+        sponsor: ctx.accounts.sponsor.key(),
+        amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::states::*;
+
+#[derive(Accounts)]
+pub struct UpdateProject<'info> {
+    #[account(
+        seeds = [
+            b"pool".as_ref(),
+            pool_account.pool_creator.as_ref(),
+            pool_account.round_id.to_le_bytes().as_ref(),
+        ],
+        bump = pool_account.bump,
+    )]
+    pub pool_account: Account<'info, Pool>,
+    #[account(
+        mut,
+        has_one = project_owner,
+        seeds = [b"project".as_ref(), pool_account.key().as_ref(), project_owner.key().as_ref()],
+        bump = project_account.bump,
+    )]
+    pub project_account: Account<'info, Project>,
+    pub project_owner: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateProject>,
+    name: Option<String>,
+    description_uri: Option<String>,
+    category: Option<String>,
+    payout: Option<Pubkey>,
+) -> Result<()> {
+    let pool_account = &ctx.accounts.pool_account;
+    let project_account = &mut ctx.accounts.project_account;
+
+    require!(!pool_account.cancelled, ErrorCode::RoundCancelled);
+    require!(!pool_account.finalized, ErrorCode::RoundFinalized);
+    require!(
+        Clock::get()?.unix_timestamp < pool_account.voting_start,
+        ErrorCode::VotingStarted
+    );
+
+    let name = name.unwrap_or_else(|| project_account.project_name.clone());
+    let description_uri =
+        description_uri.unwrap_or_else(|| project_account.description_uri.clone());
+    let category = category.unwrap_or_else(|| project_account.category.clone());
+
+    Project::validate_metadata(&name, &description_uri, &category)?;
+
+    project_account.project_name = name;
+    project_account.description_uri = description_uri;
+    project_account.category = category;
+    project_account.payout = payout.unwrap_or(project_account.payout);
+
+    emit!(ProjectUpdated {
+        pool: pool_account.key(),
+        project: project_account.key(),
+    });
+
+    Ok(())
+}
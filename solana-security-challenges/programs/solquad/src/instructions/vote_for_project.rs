@@ -0,0 +1,151 @@
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::MetadataAccount;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::qf;
+use crate::states::*;
+use crate::vault::Vault;
+
+#[derive(Accounts)]
+pub struct VoteForProject<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"pool".as_ref(),
+            pool_account.pool_creator.as_ref(),
+            pool_account.round_id.to_le_bytes().as_ref(),
+        ],
+        bump = pool_account.bump,
+    )]
+    pub pool_account: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [
+            b"project".as_ref(),
+            pool_account.key().as_ref(),
+            project_account.project_owner.as_ref(),
+        ],
+        bump = project_account.bump,
+    )]
+    pub project_account: Account<'info, Project>,
+    #[account(
+        init_if_needed,
+        payer = voter_sig,
+        space = Voter::LEN,
+        seeds = [
+            b"voter".as_ref(),
+            pool_account.key().as_ref(),
+            project_account.key().as_ref(),
+            voter_sig.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub voter_account: Account<'info, Voter>,
+    /// CHECK: vault created alongside the project
+    #[account(
+        mut,
+        seeds = [b"project-vault".as_ref(), project_account.key().as_ref()],
+        bump = project_account.vault_bump,
+    )]
+    pub project_vault: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub voter_sig: Signer<'info>,
+    #[account(mut)]
+    pub voter_token_account: Option<Account<'info, TokenAccount>>,
+    // holding of the pool's gate mint or collection NFT, only needed for gated pools
+    pub gate_token_account: Option<Account<'info, TokenAccount>>,
+    // metadata of the NFT held in `gate_token_account`, only needed for collection gated pools
+    pub gate_metadata: Option<Account<'info, MetadataAccount>>,
+    pub token_program: Option<Program<'info, Token>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<VoteForProject>,
+    amount: u64,
+    allowlist_proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let pool_account = &mut ctx.accounts.pool_account;
+    let project_account = &mut ctx.accounts.project_account;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(pool_account.is_voting(now), ErrorCode::NotInVotingPhase);
+    require!(project_account.in_pool, ErrorCode::ProjectNotInPool);
+
+    pool_account.eligibility.check(
+        &ctx.accounts.voter_sig.key(),
+        &allowlist_proof,
+        ctx.accounts.gate_token_account.as_ref(),
+        ctx.accounts.gate_metadata.as_ref(),
+    )?;
+
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    let voter_account = &mut ctx.accounts.voter_account;
+    let first_vote = voter_account.voter == Pubkey::default();
+
+    if first_vote {
+        voter_account.voter = ctx.accounts.voter_sig.key();
+        voter_account.pool = pool_account.key();
+        voter_account.voted_for = project_account.key();
+        voter_account.token_amount = 0;
+        voter_account.bump = ctx.bumps.voter_account;
+    }
+
+    // repeat votes add up into one contribution, so the voter's square root is replaced
+    // rather than added again
+    let contribution = voter_account
+        .token_amount
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let sum_sqrt = project_account
+        .sum_sqrt_contributions
+        .checked_sub(qf::sqrt_contribution(voter_account.token_amount))
+        .and_then(|sum| sum.checked_add(qf::sqrt_contribution(contribution)))
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    Vault {
+        account: &ctx.accounts.project_vault.to_account_info(),
+        mint: pool_account.vote_mint,
+        token_program: ctx.accounts.token_program.as_ref().map(|p| p.as_ref()),
+        system_program: &ctx.accounts.system_program.to_account_info(),
+    }
+    .deposit(
+        &ctx.accounts.voter_sig.to_account_info(),
+        ctx.accounts.voter_token_account.as_ref(),
+        amount,
+    )?;
+
+    project_account.sum_sqrt_contributions = sum_sqrt;
+    voter_account.token_amount = contribution;
+
+    if first_vote {
+        project_account.votes_count = project_account
+            .votes_count
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+    }
+
+    project_account.voter_amount = project_account
+        .voter_amount
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    pool_account.total_votes = pool_account
+        .total_votes
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    emit!(VoteCast {
+        pool: pool_account.key(),
+        project: project_account.key(),
+        voter: voter_account.voter,
+        amount,
+        contribution,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::states::*;
+
+#[derive(Accounts)]
+pub struct WithdrawFromPool<'info> {
+    #[account(mut, address = pool_account.escrow @ ErrorCode::EscrowMismatch)]
+    pub escrow_account: Account<'info, Escrow>,
+    #[account(
+        mut,
+        seeds = [
+            b"pool".as_ref(),
+            pool_account.pool_creator.as_ref(),
+            pool_account.round_id.to_le_bytes().as_ref(),
+        ],
+        bump = pool_account.bump,
+    )]
+    pub pool_account: Account<'info, Pool>,
+    #[account(
+        mut,
+        has_one = project_owner,
+        seeds = [b"project".as_ref(), pool_account.key().as_ref(), project_owner.key().as_ref()],
+        bump = project_account.bump,
+    )]
+    pub project_account: Account<'info, Project>,
+    pub project_owner: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<WithdrawFromPool>) -> Result<()> {
+    let escrow_account = &mut ctx.accounts.escrow_account;
+    let pool_account = &mut ctx.accounts.pool_account;
+    let project_account = &mut ctx.accounts.project_account;
+
+    require!(!pool_account.finalized, ErrorCode::RoundFinalized);
+    require!(project_account.in_pool, ErrorCode::ProjectNotInPool);
+
+    let owner = project_account.project_owner;
+    pool_account.projects.retain(|project| project != &owner);
+    escrow_account
+        .project_reciever_addresses
+        .retain(|project| project != &owner);
+    pool_account.total_projects = pool_account.total_projects.saturating_sub(1);
+
+    // votes stay in the project vault until every voter claims their refund
+    project_account.in_pool = false;
+    project_account.withdrawn = true;
+
+    emit!(ProjectWithdrawn {
+        pool: pool_account.key(),
+        project: project_account.key(),
+    });
+
+    Ok(())
+}
@@ -1,229 +1,134 @@
 use anchor_lang::prelude::*;
 
+pub mod errors;
+pub mod events;
+pub mod instructions;
+pub mod merkle;
+pub mod pairwise;
+pub mod qf;
+pub mod states;
+pub mod vault;
+
+pub use errors::ErrorCode;
+pub use events::*;
+pub use instructions::*;
+pub use states::*;
+
 declare_id!("CzyDhoJqZHfheuFbhu4sW5fXLYYyBAnf6Wrnwy7A4ghd");
 
 #[program]
 pub mod solquad {
     use super::*;
 
-    pub fn initialize_escrow(ctx: Context<InitializeEscrow>, amount: u64) -> Result<()> {
-        let escrow_account = &mut ctx.accounts.escrow_account;
-        escrow_account.escrow_creator = ctx.accounts.escrow_signer.key();
-        escrow_account.creator_deposit_amount = amount;
-        escrow_account.total_projects = 0;
-
-        Ok(())
+    pub fn initialize_registry(ctx: Context<InitializeRegistry>) -> Result<()> {
+        instructions::initialize_registry::handler(ctx)
     }
 
-    pub fn initialize_pool(ctx: Context<InitializePool>) -> Result<()> {
-        let pool_account = &mut ctx.accounts.pool_account;
-        pool_account.pool_creator = ctx.accounts.pool_signer.key();
-        pool_account.total_projects = 0;
-        pool_account.total_votes = 0;
-
-        Ok(())
+    pub fn initialize_escrow(
+        ctx: Context<InitializeEscrow>,
+        round_id: u64,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::initialize_escrow::handler(ctx, round_id, amount)
     }
 
-    pub fn initialize_project(ctx: Context<InitializeProject>, name: String) -> Result<()> {
-        let project_account = &mut ctx.accounts.project_account;
-
-        project_account.project_owner = ctx.accounts.project_owner.key();
-        project_account.project_name = name;
-        project_account.votes_count = 0;
-        project_account.voter_amount = 0;
-        project_account.distributed_amt = 0;
-        project_account.in_pool = false;
-
-        Ok(())
+    pub fn top_up_escrow(ctx: Context<TopUpEscrow>, amount: u64) -> Result<()> {
+        instructions::top_up_escrow::handler(ctx, amount)
     }
 
-    pub fn add_project_to_pool(ctx: Context<AddProjectToPool>) -> Result<()> {
-        let escrow_account = &mut ctx.accounts.escrow_account;
-        let pool_account = &mut ctx.accounts.pool_account;
-        let project_account = &mut ctx.accounts.project_account;
-        require!(
-            project_account.in_pool == false,
-            ErrorCode::ProjectExistsInPool
-        );
-        require!(
-            !pool_account.projects.iter().any(|owner| owner == &project_account.project_owner),
-            ErrorCode::ProjectExistsInPool
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize_pool(
+        ctx: Context<InitializePool>,
+        round_id: u64,
+        application_end: i64,
+        voting_start: i64,
+        voting_end: i64,
+        max_match_bps: Option<u16>,
+        matching_mode: MatchingMode,
+        eligibility: Eligibility,
+        claim_deadline: i64,
+    ) -> Result<()> {
+        instructions::initialize_pool::handler(
+            ctx,
+            round_id,
+            application_end,
+            voting_start,
+            voting_end,
+            max_match_bps,
+            matching_mode,
+            eligibility,
+            claim_deadline,
         )
-
-        pool_account.projects.push(project_account.project_owner);
-        pool_account.total_projects += 1;
-        project_account.in_pool = true;
-
-        escrow_account
-            .project_reciever_addresses
-            .push(project_account.project_owner);
-
-        Ok(())
     }
 
-    pub fn vote_for_project(ctx: Context<VoteForProject>, amount: u64) -> Result<()> {
-        let pool_account = &mut ctx.accounts.pool_account;
-        let project_account = &mut ctx.accounts.project_account;
-
-        for i in 0..pool_account.projects.len() {
-            if pool_account.projects[i] == project_account.project_owner {
-                project_account.votes_count += 1;
-                project_account.voter_amount += amount;
-            }
-        }
-
-        pool_account.total_votes += 1;
-
-        Ok(())
+    pub fn initialize_project(
+        ctx: Context<InitializeProject>,
+        name: String,
+        description_uri: String,
+        category: String,
+        payout: Pubkey,
+    ) -> Result<()> {
+        instructions::initialize_project::handler(ctx, name, description_uri, category, payout)
     }
 
-    pub fn distribute_escrow_amount(ctx: Context<DistributeEscrowAmount>) -> Result<()> {
-        let escrow_account = &mut ctx.accounts.escrow_account;
-        let pool_account = &mut ctx.accounts.pool_account;
-        let project_account = &mut ctx.accounts.project_account;
-
-        for i in 0..escrow_account.project_reciever_addresses.len() {
-            let distributable_amt: u64;
-            let votes: u64;
-
-            let project = pool_account.projects[i];
-            if project == project_account.project_owner {
-                votes = project_account.votes_count;
-            } else {
-                votes = 0;
-            }
-
-            if votes != 0 {
-                distributable_amt = votes / pool_account.total_votes)
-                    * escrow_account.creator_deposit_amount as u64;
-            } else {
-                distributable_amt = 0;
-            }
-
-            project_account.distributed_amt = distributable_amt;
-        }
-
-        Ok(())
+    pub fn update_project(
+        ctx: Context<UpdateProject>,
+        name: Option<String>,
+        description_uri: Option<String>,
+        category: Option<String>,
+        payout: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::update_project::handler(ctx, name, description_uri, category, payout)
     }
-}
 
-#[derive(Accounts)]
-pub struct InitializeEscrow<'info> {
-    #[account(
-        init,
-        payer = escrow_signer,
-        space = 1024,
-        seeds = [b"escrow".as_ref(), escrow_signer.key().as_ref()],
-        bump,
-    )]
-    pub escrow_account: Account<'info, Escrow>,
-    #[account(mut)]
-    pub escrow_signer: Signer<'info>,
-    pub system_program: Program<'info, System>,
-}
+    pub fn add_project_to_pool(ctx: Context<AddProjectToPool>) -> Result<()> {
+        instructions::add_project_to_pool::handler(ctx)
+    }
 
-#[derive(Accounts)]
-pub struct InitializePool<'info> {
-    #[account(
-        init,
-        payer = pool_signer,
-        space = 1024,
-        seeds = [b"pool".as_ref(), pool_signer.key().as_ref()],
-        bump,
-    )]
-    pub pool_account: Account<'info, Pool>,
-    #[account(mut)]
-    pub pool_signer: Signer<'info>,
-    pub system_program: Program<'info, System>,
-}
+    pub fn vote_for_project(
+        ctx: Context<VoteForProject>,
+        amount: u64,
+        allowlist_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::vote_for_project::handler(ctx, amount, allowlist_proof)
+    }
 
-#[derive(Accounts)]
-pub struct InitializeProject<'info> {
-    #[account(
-        init,
-        payer = project_owner,
-        space = 32 + 32 + 8 + 8 + 8 + 8,
-        seeds = [b"project".as_ref(), pool_account.key().as_ref(), project_owner.key().as_ref()],
-        bump,
-    )]
-    pub project_account: Account<'info, Project>,
-    #[account(mut)]
-    pub project_owner: Signer<'info>,
-    pub pool_account: Account<'info, Pool>,
-    pub system_program: Program<'info, System>,
-}
+    pub fn withdraw_from_pool(ctx: Context<WithdrawFromPool>) -> Result<()> {
+        instructions::withdraw_from_pool::handler(ctx)
+    }
 
-#[derive(Accounts)]
-pub struct AddProjectToPool<'info> {
-    #[account(mut)]
-    pub escrow_account: Account<'info, Escrow>,
-    #[account(mut)]
-    pub pool_account: Account<'info, Pool>,
-    pub project_account: Account<'info, Project>,
-    pub project_owner: Signer<'info>,
-}
+    pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
+        instructions::claim_refund::handler(ctx)
+    }
 
-#[derive(Accounts)]
-pub struct VoteForProject<'info> {
-    #[account(mut)]
-    pub pool_account: Account<'info, Pool>,
-    #[account(mut)]
-    pub project_account: Account<'info, Project>,
-    #[account(mut)]
-    pub voter_sig: Signer<'info>,
-}
+    pub fn claim_contributions(ctx: Context<ClaimContributions>) -> Result<()> {
+        instructions::claim_contributions::handler(ctx)
+    }
 
-#[derive(Accounts)]
-pub struct DistributeEscrowAmount<'info> {
-    #[account(mut)]
-    pub escrow_creator: Signer<'info>,
-    #[account(mut, has_one = escrow_creator)]
-    pub escrow_account: Account<'info, Escrow>,
-    #[account(mut)]
-    pub pool_account: Account<'info, Pool>,
-    #[account(mut)]
-    pub project_account: Account<'info, Project>,
-}
+    pub fn finalize_round(ctx: Context<FinalizeRound>) -> Result<()> {
+        instructions::finalize_round::handler(ctx)
+    }
 
-// Escrow account for quadratic funding
-#[account]
-pub struct Escrow {
-    pub escrow_creator: Pubkey,
-    pub creator_deposit_amount: u64,
-    pub total_projects: u8,
-    pub project_reciever_addresses: Vec<Pubkey>,
-}
+    pub fn tally_round<'info>(
+        ctx: Context<'_, '_, 'info, 'info, TallyRound<'info>>,
+        pairwise_scores: Vec<u128>,
+    ) -> Result<()> {
+        instructions::tally_round::handler(ctx, pairwise_scores)
+    }
 
-// Pool for each project
-#[account]
-pub struct Pool {
-    pub pool_creator: Pubkey,
-    pub projects: Vec<Pubkey>,
-    pub total_projects: u8,
-    pub total_votes: u64,
-}
+    pub fn claim_match(ctx: Context<ClaimMatch>) -> Result<()> {
+        instructions::claim_match::handler(ctx)
+    }
 
-// Projects in each pool
-#[account]
-pub struct Project {
-    pub project_owner: Pubkey,
-    pub project_name: String,
-    pub votes_count: u64,
-    pub voter_amount: u64,
-    pub distributed_amt: u64,
-    pub in_pool: bool,
-}
+    pub fn cancel_round(ctx: Context<CancelRound>) -> Result<()> {
+        instructions::cancel_round::handler(ctx)
+    }
 
-// Voters voting for the project
-#[account]
-pub struct Voter {
-    pub voter: Pubkey,
-    pub voted_for: Pubkey,
-    pub token_amount: u64,
-}
+    pub fn claim_sponsor_refund(ctx: Context<RefundSponsor>) -> Result<()> {
+        instructions::claim_sponsor_refund::handler(ctx)
+    }
 
-#[error_code]
-pub enum ErrorCode {
-    #[msg("This project already exists in the pool.")]
-    ProjectExistsInPool,
+    pub fn sweep_unclaimed(ctx: Context<RefundSponsor>) -> Result<()> {
+        instructions::sweep_unclaimed::handler(ctx)
+    }
 }
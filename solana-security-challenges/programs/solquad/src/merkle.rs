@@ -0,0 +1,67 @@
+//! Merkle allowlists of voters.
+//!
+//! Leaves are the sha256 of the voter's address and inner nodes the sha256 of
+//! their two children in sorted order, each prefixed with a distinct byte so a
+//! node can never be passed off as a leaf. Proofs are the sibling hashes from
+//! the leaf up to the root.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::hash::hashv;
+
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+/// Leaf of `voter` in an allowlist tree.
+pub fn leaf(voter: &Pubkey) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, voter.as_ref()]).to_bytes()
+}
+
+/// Parent of two nodes, independent of their order.
+pub fn node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let (first, second) = if left <= right {
+        (left, right)
+    } else {
+        (right, left)
+    };
+
+    hashv(&[NODE_PREFIX, first, second]).to_bytes()
+}
+
+/// Whether `proof` leads from `voter`'s leaf to `root`.
+pub fn verify(root: &[u8; 32], voter: &Pubkey, proof: &[[u8; 32]]) -> bool {
+    let computed = proof
+        .iter()
+        .fold(leaf(voter), |current, sibling| node(&current, sibling));
+
+    &computed == root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proofs_verify_against_the_root() {
+        let voters: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let leaves: Vec<[u8; 32]> = voters.iter().map(leaf).collect();
+
+        let left = node(&leaves[0], &leaves[1]);
+        let right = node(&leaves[2], &leaves[3]);
+        let root = node(&left, &right);
+
+        assert!(verify(&root, &voters[0], &[leaves[1], right]));
+        assert!(verify(&root, &voters[3], &[leaves[2], left]));
+
+        // wrong sibling, wrong voter and an inner node passed off as a voter
+        assert!(!verify(&root, &voters[0], &[leaves[2], right]));
+        assert!(!verify(&root, &Pubkey::new_unique(), &[leaves[1], right]));
+        assert!(!verify(&root, &voters[0], &[]));
+    }
+
+    #[test]
+    fn single_voter_tree_is_its_leaf() {
+        let voter = Pubkey::new_unique();
+
+        assert!(verify(&leaf(&voter), &voter, &[]));
+    }
+}
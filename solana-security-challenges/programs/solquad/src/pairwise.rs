@@ -0,0 +1,224 @@
+//! Pairwise bounded quadratic funding.
+//!
+//! Plain quadratic funding rewards every pair of contributors to a project with
+//! a subsidy of `2 * sqrt(c_i) * sqrt(c_j)`, which a group of accounts can farm
+//! by all funding the same projects. Pairwise matching damps each pair's
+//! subsidy by `M / (M + overlap)`, where `overlap` is how much that pair already
+//! funds together across every project of the round and `M` is a coefficient
+//! chosen by the curator. Pairs that rarely agree keep close to the full subsidy,
+//! voting rings lose most of it.
+//!
+//! Like plain quadratic funding scores, pairwise scores only hold the subsidy, a
+//! project's contributions themselves are not matched.
+//!
+//! Pairwise pools trust their creator as curator. Scores are computed off-chain
+//! from the public `Voter` records of a round with [`scores`], in the same fixed
+//! point as [`qf::subsidy`], and only the pool creator can submit them to the
+//! tally crank. The program does not recompute them: it only checks that no
+//! project scores above its plain quadratic funding subsidy, so within that bound
+//! the curator decides how matches are shared. Anyone can recompute the scores
+//! from the round's records to audit the curator.
+
+use std::collections::BTreeMap;
+
+use anchor_lang::prelude::Pubkey;
+
+use crate::qf;
+
+/// Total amount a voter contributed to a project.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Contribution {
+    pub voter: Pubkey,
+    pub project: Pubkey,
+    pub amount: u64,
+}
+
+/// Pairwise bounded subsidy of every project with at least one contribution, for the
+/// curator's `coefficient`. Contributions of the same voter to the same project are
+/// added up first, like repeat votes are on-chain.
+pub fn scores(contributions: &[Contribution], coefficient: u64) -> Option<BTreeMap<Pubkey, u128>> {
+    let mut amounts: BTreeMap<(Pubkey, Pubkey), u64> = BTreeMap::new();
+    for contribution in contributions {
+        let amount = amounts
+            .entry((contribution.project, contribution.voter))
+            .or_default();
+        *amount = amount.checked_add(contribution.amount)?;
+    }
+
+    // square roots of every voter's contribution, grouped by project
+    let mut projects: BTreeMap<Pubkey, Vec<(Pubkey, u128)>> = BTreeMap::new();
+    for (&(project, voter), &amount) in &amounts {
+        projects
+            .entry(project)
+            .or_default()
+            .push((voter, qf::sqrt_contribution(amount)));
+    }
+
+    // how much every pair of voters funds together across the round
+    let mut overlaps: BTreeMap<(Pubkey, Pubkey), u128> = BTreeMap::new();
+    for roots in projects.values() {
+        for (i, (voter_i, root_i)) in roots.iter().enumerate() {
+            for (voter_j, root_j) in &roots[i + 1..] {
+                let overlap = overlaps.entry((*voter_i, *voter_j)).or_default();
+                *overlap = overlap.checked_add(root_i.checked_mul(*root_j)?)?;
+            }
+        }
+    }
+
+    let coefficient = (coefficient as u128).checked_mul(qf::PRECISION * qf::PRECISION)?;
+
+    projects
+        .iter()
+        .map(|(project, roots)| {
+            let mut score = 0u128;
+            let mut contributions = 0u64;
+
+            for (i, (voter_i, root_i)) in roots.iter().enumerate() {
+                contributions = contributions.checked_add(amounts[&(*project, *voter_i)])?;
+                score = score.checked_add(root_i.checked_mul(*root_i)?)?;
+
+                for (voter_j, root_j) in &roots[i + 1..] {
+                    let overlap = overlaps[&(*voter_i, *voter_j)];
+                    let subsidy = qf::mul_div(
+                        root_i.checked_mul(*root_j)?,
+                        coefficient,
+                        coefficient.checked_add(overlap)?,
+                    )?;
+                    score = score.checked_add(subsidy.checked_mul(2)?)?;
+                }
+            }
+
+            // squares of the voters' own roots stand for what they paid and are
+            // taken back out with their contributions, as in `qf::subsidy`
+            Some((*project, qf::subsidy(score, contributions)?))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contribution(voter: &Pubkey, project: &Pubkey, amount: u64) -> Contribution {
+        Contribution {
+            voter: *voter,
+            project: *project,
+            amount,
+        }
+    }
+
+    fn plain_subsidy(amounts: &[u64]) -> u128 {
+        let score = qf::score(amounts.iter().map(|a| qf::sqrt_contribution(*a)).sum()).unwrap();
+        qf::subsidy(score, amounts.iter().sum()).unwrap()
+    }
+
+    #[test]
+    fn single_contributors_get_no_subsidy() {
+        let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        let scores = scores(
+            &[
+                contribution(&alice, &first, 9),
+                contribution(&bob, &second, 4),
+                contribution(&alice, &first, 7),
+            ],
+            10,
+        )
+        .unwrap();
+
+        assert_eq!(scores[&first], 0);
+        assert_eq!(scores[&second], 0);
+    }
+
+    #[test]
+    fn pair_subsidy_is_damped_by_overlap() {
+        // a pair overlapping only on this project with sqrt(1 * 1) = 1 and M = 1 keeps
+        // half of the 2 * 1 subsidy: 2 * 1/2 = 1
+        let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let project = Pubkey::new_unique();
+
+        let scores = scores(
+            &[
+                contribution(&alice, &project, 1),
+                contribution(&bob, &project, 1),
+            ],
+            1,
+        )
+        .unwrap();
+
+        assert_eq!(scores[&project], qf::PRECISION * qf::PRECISION);
+    }
+
+    #[test]
+    fn voting_ring_loses_to_independent_voters() {
+        let ring: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let crowd: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let ring_projects: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+        let honest = Pubkey::new_unique();
+
+        // the ring funds five projects together, the crowd only agrees on one
+        let mut contributions = vec![];
+        for project in &ring_projects {
+            for voter in &ring {
+                contributions.push(contribution(voter, project, 100));
+            }
+        }
+        for voter in &crowd {
+            contributions.push(contribution(voter, &honest, 100));
+        }
+
+        let scores = scores(&contributions, 100).unwrap();
+
+        // under plain qf both sets of projects would score the same, the crowd
+        // overlaps exactly M once and keeps half while the ring keeps a sixth
+        let plain = plain_subsidy(&[100; 4]);
+        assert_eq!(scores[&honest], plain / 2);
+        assert!(scores[&ring_projects[0]] <= plain / 6);
+        assert!(scores[&ring_projects[0]] > plain / 6 - qf::PRECISION);
+    }
+
+    #[test]
+    fn scores_never_exceed_plain_qf() {
+        let voters: Vec<Pubkey> = (0..6).map(|_| Pubkey::new_unique()).collect();
+        let projects: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+
+        let mut contributions = vec![];
+        let mut per_project: BTreeMap<Pubkey, Vec<u64>> = BTreeMap::new();
+        for (i, voter) in voters.iter().enumerate() {
+            for (j, project) in projects.iter().enumerate() {
+                if (i + j) % 2 == 0 {
+                    let amount = (i * 37 + j * 11 + 1) as u64;
+                    contributions.push(contribution(voter, project, amount));
+                    per_project.entry(*project).or_default().push(amount);
+                }
+            }
+        }
+
+        for coefficient in [1, 25, 1_000_000] {
+            let scores = scores(&contributions, coefficient).unwrap();
+
+            for (project, amounts) in &per_project {
+                assert!(scores[project] <= plain_subsidy(amounts));
+            }
+        }
+    }
+
+    #[test]
+    fn large_coefficient_approaches_plain_qf() {
+        let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let project = Pubkey::new_unique();
+
+        let scores = scores(
+            &[
+                contribution(&alice, &project, 4),
+                contribution(&bob, &project, 9),
+            ],
+            u32::MAX as u64,
+        )
+        .unwrap();
+
+        let plain = plain_subsidy(&[4, 9]);
+        assert!(plain - scores[&project] < qf::PRECISION * qf::PRECISION / 1_000);
+    }
+}
@@ -0,0 +1,287 @@
+//! Quadratic funding math.
+//!
+//! A project's quadratic funding score is the square of the sum of the square
+//! roots of its individual contributions. Its voters already paid the sum of
+//! their contributions, so the match it is owed is the subsidy left on top,
+//! `(Σ√c)² − Σc`, and each project receives a share of the matching pool
+//! proportional to its subsidy. Square roots are kept in fixed point with
+//! `PRECISION` digits so that small contributions still count.
+
+/// Fixed point scale of square roots, `sqrt(x)` is stored as `sqrt(x) * PRECISION`
+pub const PRECISION: u128 = 1_000;
+
+/// Denominator of basis point shares
+pub const BPS_DENOMINATOR: u128 = 10_000;
+
+/// Square root of a contribution in fixed point, rounded down.
+pub fn sqrt_contribution(amount: u64) -> u128 {
+    isqrt(amount as u128 * PRECISION * PRECISION)
+}
+
+/// Quadratic funding score of a project from the fixed point sum of the square
+/// roots of its contributions.
+pub fn score(sum_sqrt: u128) -> Option<u128> {
+    sum_sqrt.checked_mul(sum_sqrt)
+}
+
+/// Matching subsidy of a project with `score` whose voters contributed
+/// `contributions` in total, in the same fixed point as `score`. Square roots
+/// are rounded down, so a lone contribution can score slightly below its amount,
+/// which is no subsidy rather than a negative one.
+pub fn subsidy(score: u128, contributions: u64) -> Option<u128> {
+    let contributed = (contributions as u128).checked_mul(PRECISION * PRECISION)?;
+    Some(score.saturating_sub(contributed))
+}
+
+/// Share of `matching_pool` a project with `project_score` receives when all
+/// projects of the pool add up to `total_score`, rounded down.
+pub fn match_amount(matching_pool: u64, project_score: u128, total_score: u128) -> Option<u64> {
+    if project_score > total_score {
+        return None;
+    }
+
+    if total_score == 0 {
+        return Some(0);
+    }
+
+    u64::try_from(mul_div(matching_pool as u128, project_score, total_score)?).ok()
+}
+
+/// Match a project receives out of `matching_pool`.
+///
+/// Scores are the projects' subsidies, so a project's ideal match is its
+/// subsidy converted back to token units. When the
+/// ideal matches of all projects add up to more than the pool every match is
+/// scaled down proportionally, and each one is capped at `max_match_bps` of the
+/// pool when set. Whatever the cap holds back stays in the pool.
+pub fn capped_match(
+    matching_pool: u64,
+    project_score: u128,
+    total_score: u128,
+    max_match_bps: Option<u16>,
+) -> Option<u64> {
+    if project_score > total_score {
+        return None;
+    }
+
+    let scale = PRECISION * PRECISION;
+
+    let amount = if total_score / scale <= matching_pool as u128 {
+        u64::try_from(project_score / scale).ok()?
+    } else {
+        match_amount(matching_pool, project_score, total_score)?
+    };
+
+    let Some(max_match_bps) = max_match_bps else {
+        return Some(amount);
+    };
+
+    let cap = mul_div(
+        matching_pool as u128,
+        max_match_bps as u128,
+        BPS_DENOMINATOR,
+    )?;
+    Some(amount.min(u64::try_from(cap).ok()?))
+}
+
+/// Integer square root, rounded down.
+pub fn isqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+
+    // Newton's method starting above the root, every step moves closer to it
+    let mut current = 1u128 << ((128 - value.leading_zeros()).div_ceil(2));
+    loop {
+        let next = (current + value / current) / 2;
+        if next >= current {
+            return current;
+        }
+        current = next;
+    }
+}
+
+/// `a * b / c` rounded down, without overflowing on the intermediate product.
+/// Returns `None` when `c` is zero or the result doesn't fit in a `u128`.
+pub fn mul_div(a: u128, b: u128, c: u128) -> Option<u128> {
+    if c == 0 {
+        return None;
+    }
+
+    if let Some(product) = a.checked_mul(b) {
+        return Some(product / c);
+    }
+
+    let (high, low) = widening_mul(a, b);
+
+    if high >= c {
+        return None;
+    }
+
+    // long division of the 256 bit product, the remainder always stays below `c`
+    let mut remainder = high;
+    let mut quotient = 0u128;
+
+    for bit in (0..128).rev() {
+        let carry = remainder >> 127;
+        remainder = (remainder << 1) | ((low >> bit) & 1);
+        quotient <<= 1;
+
+        if carry == 1 || remainder >= c {
+            remainder = remainder.wrapping_sub(c);
+            quotient |= 1;
+        }
+    }
+
+    Some(quotient)
+}
+
+/// Full 256 bit product of `a` and `b` as `(high, low)` halves.
+fn widening_mul(a: u128, b: u128) -> (u128, u128) {
+    const MASK: u128 = u64::MAX as u128;
+
+    let (a_high, a_low) = (a >> 64, a & MASK);
+    let (b_high, b_low) = (b >> 64, b & MASK);
+
+    let low_low = a_low * b_low;
+    let low_high = a_low * b_high;
+    let high_low = a_high * b_low;
+    let high_high = a_high * b_high;
+
+    let middle = (low_low >> 64) + (low_high & MASK) + (high_low & MASK);
+
+    let low = (low_low & MASK) | (middle << 64);
+    let high = high_high + (low_high >> 64) + (high_low >> 64) + (middle >> 64);
+
+    (high, low)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project_score(contributions: &[u64]) -> u128 {
+        score(contributions.iter().map(|c| sqrt_contribution(*c)).sum()).unwrap()
+    }
+
+    fn project_subsidy(contributions: &[u64]) -> u128 {
+        subsidy(project_score(contributions), contributions.iter().sum()).unwrap()
+    }
+
+    #[test]
+    fn sqrt_is_exact_on_squares_and_rounds_down_otherwise() {
+        for root in [0u128, 1, 2, 3, 10, 65_535, u64::MAX as u128] {
+            assert_eq!(isqrt(root * root), root);
+        }
+
+        assert_eq!(isqrt(8), 2);
+        assert_eq!(isqrt(u128::MAX), u64::MAX as u128);
+        assert_eq!(sqrt_contribution(2), 1_414);
+    }
+
+    #[test]
+    fn many_small_contributions_beat_one_large() {
+        // four contributors of 1 score (4 * 1)^2 = 16, a single contributor of 4 scores 2^2 = 4
+        let crowd = project_score(&[1, 1, 1, 1]);
+        let whale = project_score(&[4]);
+
+        assert_eq!(crowd, 16 * PRECISION * PRECISION);
+        assert_eq!(whale, 4 * PRECISION * PRECISION);
+
+        assert_eq!(match_amount(100, crowd, crowd + whale), Some(80));
+        assert_eq!(match_amount(100, whale, crowd + whale), Some(20));
+    }
+
+    #[test]
+    fn score_is_square_of_sum_of_roots() {
+        // (sqrt 1 + sqrt 4 + sqrt 9)^2 = (1 + 2 + 3)^2 = 36
+        assert_eq!(project_score(&[1, 4, 9]), 36 * PRECISION * PRECISION);
+    }
+
+    #[test]
+    fn hundred_donors_against_one() {
+        // 100 donors of 1 score 100^2 = 10_000, one donor of 100 scores 10^2 = 100
+        let crowd = project_score(&[1; 100]);
+        let single = project_score(&[100]);
+        let total = crowd + single;
+
+        assert_eq!(match_amount(10_100, crowd, total), Some(10_000));
+        assert_eq!(match_amount(10_100, single, total), Some(100));
+    }
+
+    #[test]
+    fn matches_never_exceed_pool() {
+        let scores = [
+            project_score(&[3, 7]),
+            project_score(&[5]),
+            project_score(&[1, 1, 2]),
+        ];
+        let total: u128 = scores.iter().sum();
+
+        let distributed: u64 = scores
+            .iter()
+            .map(|s| match_amount(1_000_000, *s, total).unwrap())
+            .sum::<u64>();
+
+        assert!(distributed <= 1_000_000);
+        assert!(distributed >= 1_000_000 - scores.len() as u64);
+        assert_eq!(match_amount(1_000, 0, total), Some(0));
+        assert_eq!(match_amount(1_000, 0, 0), Some(0));
+        assert_eq!(match_amount(1_000, total + 1, total), None);
+    }
+
+    #[test]
+    fn subsidy_excludes_what_voters_paid() {
+        // (4 * 1)^2 - 4 = 12 and (1 + 2)^2 - 5 = 4
+        assert_eq!(project_subsidy(&[1, 1, 1, 1]), 12 * PRECISION * PRECISION);
+        assert_eq!(project_subsidy(&[1, 4]), 4 * PRECISION * PRECISION);
+
+        // a single voter is never subsidised, even when their square root rounds down
+        assert_eq!(project_subsidy(&[4]), 0);
+        assert_eq!(project_subsidy(&[2]), 0);
+        assert_eq!(subsidy(0, 0), Some(0));
+    }
+
+    #[test]
+    fn undersubscribed_pool_pays_ideal_matches() {
+        // ideal matches of 12 and 4 fit in a pool of 100, the rest stays unallocated
+        let crowd = project_subsidy(&[1, 1, 1, 1]);
+        let pair = project_subsidy(&[1, 4]);
+        let whale = project_subsidy(&[4]);
+        let total = crowd + pair + whale;
+
+        assert_eq!(capped_match(100, crowd, total, None), Some(12));
+        assert_eq!(capped_match(100, pair, total, None), Some(4));
+        assert_eq!(capped_match(100, whale, total, None), Some(0));
+    }
+
+    #[test]
+    fn oversubscribed_pool_scales_matches_down() {
+        // ideal matches of 12 and 4 scaled into a pool of 8
+        let crowd = project_subsidy(&[1, 1, 1, 1]);
+        let pair = project_subsidy(&[1, 4]);
+
+        assert_eq!(capped_match(8, crowd, crowd + pair, None), Some(6));
+        assert_eq!(capped_match(8, pair, crowd + pair, None), Some(2));
+    }
+
+    #[test]
+    fn cap_limits_a_single_project() {
+        // a 50% cap holds the crowd project back to 4 of the 8 while the other is untouched
+        let crowd = project_subsidy(&[1, 1, 1, 1]);
+        let pair = project_subsidy(&[1, 4]);
+
+        assert_eq!(capped_match(8, crowd, crowd + pair, Some(5_000)), Some(4));
+        assert_eq!(capped_match(8, pair, crowd + pair, Some(5_000)), Some(2));
+        assert_eq!(capped_match(8, crowd + 1, crowd, Some(5_000)), None);
+    }
+
+    #[test]
+    fn mul_div_handles_wide_products() {
+        assert_eq!(mul_div(u128::MAX, u128::MAX, u128::MAX), Some(u128::MAX));
+        assert_eq!(mul_div(u128::MAX, 2, 4), Some(u128::MAX / 2));
+        assert_eq!(mul_div(1 << 100, 1 << 100, 1 << 90), Some(1 << 110));
+        assert_eq!(mul_div(u128::MAX, u128::MAX, 1), None);
+        assert_eq!(mul_div(1, 1, 0), None);
+    }
+}
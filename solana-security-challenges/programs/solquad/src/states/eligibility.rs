@@ -0,0 +1,156 @@
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::MetadataAccount;
+use anchor_spl::token::TokenAccount;
+
+use crate::errors::ErrorCode;
+use crate::merkle;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Eligibility {
+    // anyone can vote
+    Open,
+    // voters prove they are part of a merkle allowlist, see `merkle`
+    Allowlist { root: [u8; 32] },
+    // voters hold at least `min_balance` of `mint`
+    TokenGate { mint: Pubkey, min_balance: u64 },
+    // voters hold an NFT whose metadata is a verified member of `collection`
+    //
+    // holdings are checked when each vote is cast, not snapshotted or locked, so a
+    // holder can move the same tokens or NFT through several wallets and vote from
+    // each of them. Use an allowlist when one vote per holder matters.
+    Collection { collection: Pubkey },
+}
+
+impl Eligibility {
+    pub fn check(
+        &self,
+        voter: &Pubkey,
+        allowlist_proof: &[[u8; 32]],
+        gate_token_account: Option<&Account<TokenAccount>>,
+        gate_metadata: Option<&Account<MetadataAccount>>,
+    ) -> Result<()> {
+        match self {
+            Eligibility::Open => Ok(()),
+            Eligibility::Allowlist { root } => {
+                require!(
+                    merkle::verify(root, voter, allowlist_proof),
+                    ErrorCode::VoterNotEligible
+                );
+                Ok(())
+            }
+            Eligibility::TokenGate { mint, min_balance } => {
+                let holding = gate_token_account.ok_or(ErrorCode::VoterNotEligible)?;
+                require!(
+                    holding.mint == *mint
+                        && holding.owner == *voter
+                        && holding.amount >= *min_balance,
+                    ErrorCode::VoterNotEligible
+                );
+                Ok(())
+            }
+            Eligibility::Collection { collection } => {
+                let holding = gate_token_account.ok_or(ErrorCode::VoterNotEligible)?;
+                // only the metadata program can write accounts it owns, so its mint can be trusted
+                let metadata = gate_metadata.ok_or(ErrorCode::VoterNotEligible)?;
+                let verified = metadata
+                    .collection
+                    .as_ref()
+                    .is_some_and(|member| member.verified && member.key == *collection);
+                require!(
+                    verified
+                        && metadata.mint == holding.mint
+                        && holding.owner == *voter
+                        && holding.amount > 0,
+                    ErrorCode::VoterNotEligible
+                );
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collection_gate_requires_a_verified_member_held_by_the_voter() {
+        use anchor_lang::solana_program::program_pack::Pack;
+        use anchor_spl::metadata::mpl_token_metadata::types;
+        use anchor_spl::token::spl_token;
+
+        let (voter, mint, collection) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let gate = Eligibility::Collection { collection };
+
+        let token_data = |owner: Pubkey| {
+            let mut data = vec![0; spl_token::state::Account::LEN];
+            spl_token::state::Account {
+                mint,
+                owner,
+                amount: 1,
+                state: spl_token::state::AccountState::Initialized,
+                ..Default::default()
+            }
+            .pack_into_slice(&mut data);
+            data
+        };
+        // borsh layout of a MetadataV1 account with empty strings and no optional fields
+        // besides its collection
+        let metadata_data = |verified: bool| {
+            let mut data = vec![types::Key::MetadataV1 as u8];
+            data.extend_from_slice(Pubkey::new_unique().as_ref());
+            data.extend_from_slice(mint.as_ref());
+            // name, symbol and uri
+            data.extend_from_slice(&[0; 4 * 3]);
+            data.extend_from_slice(&0u16.to_le_bytes());
+            // creators, primary sale, mutable, edition nonce and token standard
+            data.extend_from_slice(&[0, 0, 1, 0, 0]);
+            data.extend_from_slice(&[1, verified as u8]);
+            data.extend_from_slice(collection.as_ref());
+            // uses, collection details and programmable config
+            data.extend_from_slice(&[0, 0, 0]);
+            data
+        };
+
+        let check = |token: Vec<u8>, metadata: Vec<u8>| {
+            let (token_key, metadata_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+            let (mut token, mut metadata) = (token, metadata);
+            let (mut token_lamports, mut metadata_lamports) = (1, 1);
+            let metadata_owner = MetadataAccount::owner();
+            let token_info = AccountInfo::new(
+                &token_key,
+                false,
+                false,
+                &mut token_lamports,
+                &mut token,
+                &spl_token::ID,
+                false,
+                0,
+            );
+            let metadata_info = AccountInfo::new(
+                &metadata_key,
+                false,
+                false,
+                &mut metadata_lamports,
+                &mut metadata,
+                &metadata_owner,
+                false,
+                0,
+            );
+            let token = Account::<TokenAccount>::try_from(&token_info).unwrap();
+            let metadata = Account::<MetadataAccount>::try_from(&metadata_info).unwrap();
+
+            gate.check(&voter, &[], Some(&token), Some(&metadata))
+        };
+
+        assert!(check(token_data(voter), metadata_data(true)).is_ok());
+        // unverified members and NFTs held by someone else don't count
+        assert!(check(token_data(voter), metadata_data(false)).is_err());
+        assert!(check(token_data(Pubkey::new_unique()), metadata_data(true)).is_err());
+        assert!(gate.check(&voter, &[], None, None).is_err());
+    }
+}
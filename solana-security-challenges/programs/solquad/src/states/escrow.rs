@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+// Escrow account for quadratic funding
+#[account]
+pub struct Escrow {
+    pub escrow_creator: Pubkey,
+    pub creator_deposit_amount: u64,
+    pub total_projects: u8,
+    pub project_reciever_addresses: Vec<Pubkey>,
+    // creator deposit and every sponsor top up
+    pub total_deposits: u64,
+    // mint the matching funds are held in, lamports when unset
+    pub mint: Option<Pubkey>,
+    pub vault_bump: u8,
+    pub bump: u8,
+    // round of the creator the escrow funds, and its pool once opened
+    pub round_id: u64,
+    pub pool: Pubkey,
+}
+
+impl Escrow {
+    // an escrow funding a single pool lists at most every project of the pool
+    pub fn space(projects: usize) -> usize {
+        8 + 32 + 8 + 1 + (4 + 32 * projects) + 8 + (1 + 32) + 1 + 1 + 8 + 32
+    }
+}
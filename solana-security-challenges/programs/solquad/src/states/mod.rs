@@ -0,0 +1,121 @@
+pub mod eligibility;
+pub mod escrow;
+pub mod pool;
+pub mod project;
+pub mod round_registry;
+pub mod sponsor;
+pub mod voter;
+
+pub use eligibility::*;
+pub use escrow::*;
+pub use pool::*;
+pub use project::*;
+pub use round_registry::*;
+pub use sponsor::*;
+pub use voter::*;
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::prelude::*;
+
+    use super::*;
+
+    fn serialized_len<T: AnchorSerialize>(account: &T) -> usize {
+        8 + account.try_to_vec().unwrap().len()
+    }
+
+    #[test]
+    fn spaces_match_serialized_accounts() {
+        let projects = vec![Pubkey::new_unique(); 3];
+
+        let escrow = Escrow {
+            escrow_creator: Pubkey::new_unique(),
+            creator_deposit_amount: 0,
+            total_projects: 3,
+            project_reciever_addresses: projects.clone(),
+            total_deposits: 0,
+            mint: Some(Pubkey::new_unique()),
+            vault_bump: 0,
+            bump: 0,
+            round_id: 0,
+            pool: Pubkey::new_unique(),
+        };
+        assert_eq!(serialized_len(&escrow), Escrow::space(projects.len()));
+
+        let registry = RoundRegistry {
+            creator: Pubkey::new_unique(),
+            rounds: projects.clone(),
+            bump: 0,
+        };
+        assert_eq!(
+            serialized_len(&registry),
+            RoundRegistry::space(projects.len())
+        );
+
+        let pool = Pool {
+            pool_creator: Pubkey::new_unique(),
+            escrow: Pubkey::new_unique(),
+            projects: projects.clone(),
+            total_projects: 3,
+            total_votes: 0,
+            total_score: 0,
+            tallied_projects: 0,
+            matching_pool: 0,
+            vote_mint: Some(Pubkey::new_unique()),
+            application_end: 0,
+            voting_start: 0,
+            voting_end: 0,
+            finalized: false,
+            bump: 0,
+            max_match_bps: Some(0),
+            matching_mode: MatchingMode::Pairwise,
+            eligibility: Eligibility::TokenGate {
+                mint: Pubkey::new_unique(),
+                min_balance: 0,
+            },
+            claim_deadline: 0,
+            cancelled: false,
+            refund_pool: Some(0),
+            refunded_deposits: 0,
+            round_id: 0,
+        };
+        assert_eq!(serialized_len(&pool), Pool::space(projects.len()));
+
+        let project = Project {
+            project_owner: Pubkey::new_unique(),
+            project_name: "n".repeat(Project::MAX_NAME_LEN),
+            votes_count: 0,
+            voter_amount: 0,
+            distributed_amt: 0,
+            in_pool: false,
+            sum_sqrt_contributions: 0,
+            vault_bump: 0,
+            match_paid: false,
+            tallied: false,
+            bump: 0,
+            score: 0,
+            description_uri: "u".repeat(Project::MAX_URI_LEN),
+            category: "c".repeat(Project::MAX_CATEGORY_LEN),
+            payout: Pubkey::new_unique(),
+            withdrawn: false,
+        };
+        assert_eq!(serialized_len(&project), Project::LEN);
+
+        let voter = Voter {
+            voter: Pubkey::new_unique(),
+            pool: Pubkey::new_unique(),
+            voted_for: Pubkey::new_unique(),
+            token_amount: 0,
+            bump: 0,
+        };
+        assert_eq!(serialized_len(&voter), Voter::LEN);
+
+        let sponsor = Sponsor {
+            sponsor: Pubkey::new_unique(),
+            escrow: Pubkey::new_unique(),
+            amount: 0,
+            bump: 0,
+        };
+        assert_eq!(serialized_len(&sponsor), Sponsor::LEN);
+    }
+}
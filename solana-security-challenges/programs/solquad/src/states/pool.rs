@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+
+use super::Eligibility;
+
+// Pool for each project
+#[account]
+pub struct Pool {
+    pub pool_creator: Pubkey,
+    // escrow holding the matching funds of the round
+    pub escrow: Pubkey,
+    pub projects: Vec<Pubkey>,
+    pub total_projects: u8,
+    pub total_votes: u64,
+    // sum of the quadratic funding subsidies of the projects tallied so far
+    pub total_score: u128,
+    pub tallied_projects: u8,
+    // escrow deposits shared out once every project is tallied
+    pub matching_pool: u64,
+    // mint votes are cast in, lamports when unset
+    pub vote_mint: Option<Pubkey>,
+    // projects can join until `application_end`, votes are accepted in [voting_start, voting_end)
+    pub application_end: i64,
+    pub voting_start: i64,
+    pub voting_end: i64,
+    // results are frozen once the round is finalized after voting ends
+    pub finalized: bool,
+    pub bump: u8,
+    // share of the matching pool a single project can receive at most, uncapped when unset
+    pub max_match_bps: Option<u16>,
+    pub matching_mode: MatchingMode,
+    pub eligibility: Eligibility,
+    // matches can be claimed until the deadline, leftovers are then swept back to sponsors
+    pub claim_deadline: i64,
+    // cancelled rounds refund voters and sponsors
+    pub cancelled: bool,
+    // escrow left to share between sponsors, set by the first sponsor refund
+    pub refund_pool: Option<u64>,
+    // deposits of the sponsors refunded so far, the last one is paid whatever is left
+    pub refunded_deposits: u64,
+    // id of the round among the creator's rounds, part of the pool address
+    pub round_id: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MatchingMode {
+    // matches follow the plain quadratic funding score of each project
+    Quadratic,
+    // the pool creator submits each project's score at tally, trusted up to its plain
+    // quadratic funding subsidy, see `pairwise`
+    Pairwise,
+}
+
+impl Pool {
+    pub const MAX_PROJECTS: usize = 100;
+
+    // the account grows with every project added, up to `MAX_PROJECTS`
+    pub fn space(projects: usize) -> usize {
+        // discriminator, creator, escrow and the projects list
+        let header = 8 + 32 + 32 + (4 + 32 * projects);
+        // counters and tally results
+        let tally = 1 + 8 + 16 + 1 + 8;
+        // vote mint, round windows, finalized, bump, cap, matching mode and eligibility
+        let settings = (1 + 32) + 8 + 8 + 8 + 1 + 1 + (1 + 2) + 1 + (1 + 32 + 8);
+        // claim deadline, cancelled, refund pool and refunded deposits
+        let refunds = 8 + 1 + (1 + 8) + 8;
+        let round_id = 8;
+
+        header + tally + settings + refunds + round_id
+    }
+
+    pub fn accepts_projects(&self, now: i64) -> bool {
+        !self.finalized && !self.cancelled && now < self.application_end
+    }
+
+    pub fn is_voting(&self, now: i64) -> bool {
+        !self.finalized && !self.cancelled && self.voting_start <= now && now < self.voting_end
+    }
+
+    pub fn is_tallied(&self) -> bool {
+        self.finalized && !self.cancelled && self.tallied_projects == self.total_projects
+    }
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+// Projects in each pool
+#[account]
+pub struct Project {
+    pub project_owner: Pubkey,
+    pub project_name: String,
+    pub votes_count: u64,
+    // sum of every contribution, which `qf::subsidy` takes back out of the score
+    pub voter_amount: u64,
+    pub distributed_amt: u64,
+    pub in_pool: bool,
+    // sum of the square roots of every contribution, in `qf::PRECISION` fixed point
+    pub sum_sqrt_contributions: u128,
+    pub vault_bump: u8,
+    pub match_paid: bool,
+    // set once the project's score is counted into the pool
+    pub tallied: bool,
+    pub bump: u8,
+    // subsidy the project was tallied with, in `qf::score` fixed point
+    pub score: u128,
+    pub description_uri: String,
+    pub category: String,
+    // receives the contributions and match, can differ from the owner
+    pub payout: Pubkey,
+    // withdrawn projects leave the pool and refund their voters
+    pub withdrawn: bool,
+}
+
+impl Project {
+    // in bytes
+    pub const MAX_NAME_LEN: usize = 64;
+    pub const MAX_URI_LEN: usize = 200;
+    pub const MAX_CATEGORY_LEN: usize = 32;
+
+    pub const LEN: usize = 8 // discriminator
+        + 32 // project_owner
+        + (4 + Self::MAX_NAME_LEN) // project_name
+        + 8 // votes_count
+        + 8 // voter_amount
+        + 8 // distributed_amt
+        + 1 // in_pool
+        + 16 // sum_sqrt_contributions
+        + 1 // vault_bump
+        + 1 // match_paid
+        + 1 // tallied
+        + 1 // bump
+        + 16 // score
+        + (4 + Self::MAX_URI_LEN) // description_uri
+        + (4 + Self::MAX_CATEGORY_LEN) // category
+        + 32 // payout
+        + 1; // withdrawn
+
+    pub fn validate_metadata(name: &str, description_uri: &str, category: &str) -> Result<()> {
+        require!(
+            name.len() <= Self::MAX_NAME_LEN,
+            ErrorCode::ProjectNameTooLong
+        );
+        require!(
+            description_uri.len() <= Self::MAX_URI_LEN && category.len() <= Self::MAX_CATEGORY_LEN,
+            ErrorCode::ProjectMetadataTooLong
+        );
+
+        Ok(())
+    }
+}
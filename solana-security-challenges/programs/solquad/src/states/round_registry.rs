@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+// Rounds opened by a creator, the pool of round `n` is at index `n`
+#[account]
+pub struct RoundRegistry {
+    pub creator: Pubkey,
+    pub rounds: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl RoundRegistry {
+    // the registry grows with every round opened
+    pub fn space(rounds: usize) -> usize {
+        8 + 32 + (4 + 32 * rounds) + 1
+    }
+}
@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+// Sponsor contributing to the matching funds of an escrow
+#[account]
+pub struct Sponsor {
+    pub sponsor: Pubkey,
+    pub escrow: Pubkey,
+    pub amount: u64,
+    pub bump: u8,
+}
+
+impl Sponsor {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 1;
+}
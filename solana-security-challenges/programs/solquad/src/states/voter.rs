@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+// Voters voting for the project, one per voter and project of a pool
+#[account]
+pub struct Voter {
+    pub voter: Pubkey,
+    pub pool: Pubkey,
+    pub voted_for: Pubkey,
+    // sum of every vote the voter cast for the project
+    pub token_amount: u64,
+    pub bump: u8,
+}
+
+impl Voter {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 1;
+}
@@ -0,0 +1,224 @@
+//! Program controlled vaults holding either lamports or tokens of a mint.
+//!
+//! A vault is a PDA which signs for itself. Without a mint it is a plain system
+//! account kept rent exempt, with a mint it is a token account of that mint whose
+//! authority is the vault address itself.
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Allocate, Assign, CreateAccount, Transfer};
+use anchor_spl::token::{self, InitializeAccount3, TokenAccount};
+
+use crate::ErrorCode;
+
+pub struct Vault<'a, 'info> {
+    pub account: &'a AccountInfo<'info>,
+    pub mint: Option<Pubkey>,
+    pub token_program: Option<&'a AccountInfo<'info>>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> Vault<'a, 'info> {
+    /// Creates the vault account, `payer` funds its rent.
+    pub fn create(
+        &self,
+        seeds: &[&[u8]],
+        mint: Option<&AccountInfo<'info>>,
+        payer: &AccountInfo<'info>,
+    ) -> Result<()> {
+        let rent = Rent::get()?;
+
+        let Some(mint_key) = self.mint else {
+            return system_program::transfer(
+                CpiContext::new(
+                    self.system_program.clone(),
+                    Transfer {
+                        from: payer.clone(),
+                        to: self.account.clone(),
+                    },
+                ),
+                rent.minimum_balance(0),
+            );
+        };
+
+        let mint = mint.ok_or(ErrorCode::InvalidVaultMint)?;
+        require_keys_eq!(mint.key(), mint_key, ErrorCode::InvalidVaultMint);
+
+        // like anchor's `init`, an address someone already sent lamports to is topped
+        // up and claimed rather than failing to be created
+        let lamports = self.account.lamports();
+        if lamports == 0 {
+            system_program::create_account(
+                CpiContext::new_with_signer(
+                    self.system_program.clone(),
+                    CreateAccount {
+                        from: payer.clone(),
+                        to: self.account.clone(),
+                    },
+                    &[seeds],
+                ),
+                rent.minimum_balance(TokenAccount::LEN),
+                TokenAccount::LEN as u64,
+                &token::ID,
+            )?;
+        } else {
+            let shortfall = rent
+                .minimum_balance(TokenAccount::LEN)
+                .saturating_sub(lamports);
+            if shortfall > 0 {
+                system_program::transfer(
+                    CpiContext::new(
+                        self.system_program.clone(),
+                        Transfer {
+                            from: payer.clone(),
+                            to: self.account.clone(),
+                        },
+                    ),
+                    shortfall,
+                )?;
+            }
+
+            system_program::allocate(
+                CpiContext::new_with_signer(
+                    self.system_program.clone(),
+                    Allocate {
+                        account_to_allocate: self.account.clone(),
+                    },
+                    &[seeds],
+                ),
+                TokenAccount::LEN as u64,
+            )?;
+            system_program::assign(
+                CpiContext::new_with_signer(
+                    self.system_program.clone(),
+                    Assign {
+                        account_to_assign: self.account.clone(),
+                    },
+                    &[seeds],
+                ),
+                &token::ID,
+            )?;
+        }
+
+        token::initialize_account3(CpiContext::new(
+            self.token_program()?.clone(),
+            InitializeAccount3 {
+                account: self.account.clone(),
+                mint: mint.clone(),
+                authority: self.account.clone(),
+            },
+        ))
+    }
+
+    /// Moves `amount` from the `depositor` signer into the vault, out of `depositor_token`
+    /// when the vault holds tokens.
+    pub fn deposit(
+        &self,
+        depositor: &AccountInfo<'info>,
+        depositor_token: Option<&Account<'info, TokenAccount>>,
+        amount: u64,
+    ) -> Result<()> {
+        if self.mint.is_none() {
+            return system_program::transfer(
+                CpiContext::new(
+                    self.system_program.clone(),
+                    Transfer {
+                        from: depositor.clone(),
+                        to: self.account.clone(),
+                    },
+                ),
+                amount,
+            );
+        }
+
+        let depositor_token = self.token_account(depositor_token)?;
+
+        token::transfer(
+            CpiContext::new(
+                self.token_program()?.clone(),
+                token::Transfer {
+                    from: depositor_token.to_account_info(),
+                    to: self.account.clone(),
+                    authority: depositor.clone(),
+                },
+            ),
+            amount,
+        )
+    }
+
+    /// Pays `amount` out of the vault to `recipient`, into `recipient_token` when the
+    /// vault holds tokens.
+    pub fn withdraw(
+        &self,
+        seeds: &[&[u8]],
+        recipient: &AccountInfo<'info>,
+        recipient_token: Option<&Account<'info, TokenAccount>>,
+        amount: u64,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        if self.mint.is_none() {
+            return system_program::transfer(
+                CpiContext::new_with_signer(
+                    self.system_program.clone(),
+                    Transfer {
+                        from: self.account.clone(),
+                        to: recipient.clone(),
+                    },
+                    &[seeds],
+                ),
+                amount,
+            );
+        }
+
+        let recipient_token = self.token_account(recipient_token)?;
+        require_keys_eq!(
+            recipient_token.owner,
+            recipient.key(),
+            ErrorCode::InvalidTokenAccount
+        );
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.token_program()?.clone(),
+                token::Transfer {
+                    from: self.account.clone(),
+                    to: recipient_token.to_account_info(),
+                    authority: self.account.clone(),
+                },
+                &[seeds],
+            ),
+            amount,
+        )
+    }
+
+    /// Amount the vault can pay out, lamports kept for rent exemption excluded.
+    pub fn balance(&self) -> Result<u64> {
+        if self.mint.is_none() {
+            let rent = Rent::get()?.minimum_balance(0);
+            return Ok(self.account.lamports().saturating_sub(rent));
+        }
+
+        let data = self.account.try_borrow_data()?;
+        Ok(TokenAccount::try_deserialize(&mut &data[..])?.amount)
+    }
+
+    fn token_account<'b>(
+        &self,
+        token_account: Option<&'b Account<'info, TokenAccount>>,
+    ) -> Result<&'b Account<'info, TokenAccount>> {
+        let token_account = token_account.ok_or(ErrorCode::InvalidTokenAccount)?;
+        require!(
+            Some(token_account.mint) == self.mint,
+            ErrorCode::InvalidTokenAccount
+        );
+
+        Ok(token_account)
+    }
+
+    fn token_program(&self) -> Result<&'a AccountInfo<'info>> {
+        self.token_program
+            .ok_or_else(|| ErrorCode::MissingTokenProgram.into())
+    }
+}
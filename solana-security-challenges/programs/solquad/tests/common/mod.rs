@@ -0,0 +1,331 @@
+//! solquad in a `solana-program-test` bank, next to the SPL token program.
+//!
+//! Under `cargo test` the program is registered as a native processor. Under
+//! `cargo test-sbf`, or with `SBF_OUT_DIR` pointing at the output of `anchor build`,
+//! program-test loads `solquad.so` instead and every test runs on the real loader.
+
+#![allow(dead_code)]
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Once;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+use anchor_lang::solana_program::{program_pack::Pack, system_program};
+use anchor_lang::Discriminator;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::spl_token;
+use base64::prelude::{Engine, BASE64_STANDARD};
+use solana_program_test::{processor, ProgramTest, ProgramTestContext};
+use solana_sdk::account::{Account as SolanaAccount, AccountSharedData};
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+pub const SOL: u64 = 1_000_000_000;
+
+pub struct Bank {
+    context: ProgramTestContext,
+    signers: HashMap<Pubkey, Keypair>,
+    sent: HashSet<Signature>,
+    events: Vec<Vec<u8>>,
+}
+
+impl Bank {
+    pub async fn new(now: i64) -> Self {
+        let program = ProgramTest::new("solquad", solquad::ID, processor!(process));
+        let context = program.start_with_context().await;
+
+        // program-test installs its syscall stubs while starting the first bank
+        static LOG_DATA: Once = Once::new();
+        LOG_DATA.call_once(|| {
+            let inner = set_syscall_stubs(Box::new(Unset));
+            set_syscall_stubs(Box::new(LogData(inner)));
+        });
+
+        let mut bank = Bank {
+            context,
+            signers: HashMap::new(),
+            sent: HashSet::new(),
+            events: vec![],
+        };
+        bank.warp(now).await;
+
+        bank
+    }
+
+    /// Moves the clock to `now`.
+    pub async fn warp(&mut self, now: i64) {
+        let mut clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp = now;
+        self.context.set_sysvar(&clock);
+    }
+
+    /// Creates a system account holding `lamports` whose key signs the transactions
+    /// it is a signer of.
+    pub fn signer(&mut self, lamports: u64) -> Pubkey {
+        let keypair = Keypair::new();
+        let key = keypair.pubkey();
+        self.fund(&key, lamports);
+        self.signers.insert(key, keypair);
+        key
+    }
+
+    /// Creates a system account holding `lamports`.
+    pub fn fund(&mut self, key: &Pubkey, lamports: u64) {
+        self.context.set_account(
+            key,
+            &AccountSharedData::new(lamports, 0, &system_program::ID),
+        );
+    }
+
+    /// Stores `data` at `key` as an account owned by `owner`.
+    pub fn set_account(&mut self, key: &Pubkey, owner: &Pubkey, data: Vec<u8>) {
+        let lamports = Rent::default().minimum_balance(data.len());
+        self.context.set_account(
+            key,
+            &SolanaAccount {
+                lamports,
+                data,
+                owner: *owner,
+                executable: false,
+                rent_epoch: 0,
+            }
+            .into(),
+        );
+    }
+
+    pub async fn lamports(&mut self, key: &Pubkey) -> u64 {
+        self.get(key).await.map_or(0, |account| account.lamports)
+    }
+
+    pub async fn exists(&mut self, key: &Pubkey) -> bool {
+        self.get(key).await.is_some()
+    }
+
+    pub async fn account<T: AccountDeserialize>(&mut self, key: &Pubkey) -> T {
+        let account = self.get(key).await.unwrap();
+        T::try_deserialize(&mut &account.data[..]).unwrap()
+    }
+
+    async fn get(&mut self, key: &Pubkey) -> Option<SolanaAccount> {
+        self.context.banks_client.get_account(*key).await.unwrap()
+    }
+
+    /// Creates a mint without a mint authority.
+    pub fn mint(&mut self) -> Pubkey {
+        let mint = Pubkey::new_unique();
+        let mut data = vec![0; spl_token::state::Mint::LEN];
+        spl_token::state::Mint {
+            is_initialized: true,
+            ..Default::default()
+        }
+        .pack_into_slice(&mut data);
+        self.set_account(&mint, &spl_token::ID, data);
+
+        mint
+    }
+
+    /// Creates the associated token account of `owner` for `mint` holding `amount`.
+    pub fn token_account(&mut self, mint: &Pubkey, owner: &Pubkey, amount: u64) -> Pubkey {
+        let key = get_associated_token_address(owner, mint);
+        let mut data = vec![0; spl_token::state::Account::LEN];
+        spl_token::state::Account {
+            mint: *mint,
+            owner: *owner,
+            amount,
+            state: spl_token::state::AccountState::Initialized,
+            ..Default::default()
+        }
+        .pack_into_slice(&mut data);
+        self.set_account(&key, &spl_token::ID, data);
+
+        key
+    }
+
+    pub async fn token_balance(&mut self, key: &Pubkey) -> u64 {
+        let account = self.get(key).await.unwrap();
+        spl_token::state::Account::unpack(&account.data)
+            .unwrap()
+            .amount
+    }
+
+    /// Takes the events of type `T` emitted so far, oldest first.
+    pub fn events<T: Discriminator + AnchorDeserialize>(&mut self) -> Vec<T> {
+        let (matching, rest): (Vec<_>, Vec<_>) = self
+            .events
+            .drain(..)
+            .partition(|data| data.starts_with(&T::DISCRIMINATOR));
+        self.events = rest;
+
+        matching
+            .iter()
+            .map(|data| T::deserialize(&mut &data[8..]).unwrap())
+            .collect()
+    }
+
+    /// Runs `instruction` as a transaction signed by every signer it lists. Events are
+    /// only kept when it succeeds.
+    pub async fn process(
+        &mut self,
+        instruction: Instruction,
+    ) -> std::result::Result<(), ProgramError> {
+        let payer = self.context.payer.pubkey();
+        let mut blockhash = self.context.last_blockhash;
+
+        let transaction = loop {
+            let mut signers: Vec<&Keypair> = vec![&self.context.payer];
+            for meta in &instruction.accounts {
+                if meta.is_signer && meta.pubkey != payer {
+                    if let Some(keypair) = self.signers.get(&meta.pubkey) {
+                        if !signers.iter().any(|s| s.pubkey() == meta.pubkey) {
+                            signers.push(keypair);
+                        }
+                    }
+                }
+            }
+
+            let transaction = Transaction::new_signed_with_payer(
+                std::slice::from_ref(&instruction),
+                Some(&payer),
+                &signers,
+                blockhash,
+            );
+
+            // a transaction identical to an earlier one needs a new blockhash to run again
+            if self.sent.insert(transaction.signatures[0]) {
+                break transaction;
+            }
+            blockhash = self.context.get_new_latest_blockhash().await.unwrap();
+        };
+
+        let outcome = self
+            .context
+            .banks_client
+            .process_transaction_with_metadata(transaction)
+            .await
+            .unwrap();
+
+        match outcome.result {
+            Ok(()) => {
+                let logs = outcome.metadata.map(|m| m.log_messages).unwrap_or_default();
+                self.events.extend(logs.iter().filter_map(|log| {
+                    let data = log
+                        .strip_prefix("Program data: ")
+                        .or_else(|| log.strip_prefix("Program log: Program data: "))?;
+                    BASE64_STANDARD.decode(data).ok()
+                }));
+                Ok(())
+            }
+            Err(TransactionError::InstructionError(_, error)) => Err(ProgramError::try_from(error)
+                .unwrap_or_else(|error| panic!("unexpected instruction error {error:?}"))),
+            Err(error) => panic!("transaction failed: {error}"),
+        }
+    }
+}
+
+// Anchor ties the account infos to the lifetime of the slice holding them, native
+// processors are handed a shorter lived slice.
+fn process(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    solquad::entry(program_id, accounts, data)
+}
+
+// Stubs installed while swapping in `LogData`.
+struct Unset;
+
+impl SyscallStubs for Unset {}
+
+// program-test's native stubs print `sol_log_data` to stdout, this logs events the
+// way the loader does so they end up in the transaction logs.
+struct LogData(Box<dyn SyscallStubs>);
+
+impl SyscallStubs for LogData {
+    fn sol_log(&self, message: &str) {
+        self.0.sol_log(message)
+    }
+
+    fn sol_log_compute_units(&self) {
+        self.0.sol_log_compute_units()
+    }
+
+    fn sol_remaining_compute_units(&self) -> u64 {
+        self.0.sol_remaining_compute_units()
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        self.0
+            .sol_invoke_signed(instruction, account_infos, signers_seeds)
+    }
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.0.sol_get_clock_sysvar(var_addr)
+    }
+
+    fn sol_get_epoch_schedule_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.0.sol_get_epoch_schedule_sysvar(var_addr)
+    }
+
+    fn sol_get_fees_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.0.sol_get_fees_sysvar(var_addr)
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.0.sol_get_rent_sysvar(var_addr)
+    }
+
+    fn sol_get_epoch_rewards_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.0.sol_get_epoch_rewards_sysvar(var_addr)
+    }
+
+    fn sol_get_last_restart_slot(&self, var_addr: *mut u8) -> u64 {
+        self.0.sol_get_last_restart_slot(var_addr)
+    }
+
+    unsafe fn sol_memcpy(&self, dst: *mut u8, src: *const u8, n: usize) {
+        self.0.sol_memcpy(dst, src, n)
+    }
+
+    unsafe fn sol_memmove(&self, dst: *mut u8, src: *const u8, n: usize) {
+        self.0.sol_memmove(dst, src, n)
+    }
+
+    unsafe fn sol_memcmp(&self, s1: *const u8, s2: *const u8, n: usize, result: *mut i32) {
+        self.0.sol_memcmp(s1, s2, n, result)
+    }
+
+    unsafe fn sol_memset(&self, s: *mut u8, c: u8, n: usize) {
+        self.0.sol_memset(s, c, n)
+    }
+
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        self.0.sol_get_return_data()
+    }
+
+    fn sol_set_return_data(&self, data: &[u8]) {
+        self.0.sol_set_return_data(data)
+    }
+
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        let fields: Vec<String> = fields
+            .iter()
+            .map(|field| BASE64_STANDARD.encode(field))
+            .collect();
+        self.0
+            .sol_log(&format!("Program data: {}", fields.join(" ")))
+    }
+
+    fn sol_get_processed_sibling_instruction(&self, index: usize) -> Option<Instruction> {
+        self.0.sol_get_processed_sibling_instruction(index)
+    }
+
+    fn sol_get_stack_height(&self) -> u64 {
+        self.0.sol_get_stack_height()
+    }
+}
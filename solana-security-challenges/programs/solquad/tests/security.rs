@@ -0,0 +1,1523 @@
+//! Regressions for the vulnerabilities the challenge shipped with, run in a
+//! program-test bank.
+
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::system_program;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::metadata::{mpl_token_metadata, MetadataAccount};
+use anchor_spl::token;
+use common::{Bank, SOL};
+use solana_program_test::tokio;
+use solquad::pairwise::Contribution;
+use solquad::{
+    accounts, instruction, merkle, pairwise, qf, Eligibility, ErrorCode, Escrow, MatchClaimed,
+    MatchingMode, Pool, Project, ProjectAdded, ProjectTallied, ProjectUpdated, ProjectWithdrawn,
+    RefundClaimed, RegistryInitialized, RoundRegistry, SponsorRefunded, VoteCast, Voter,
+};
+
+const APPLICATIONS_OPEN: i64 = 0;
+const APPLICATION_END: i64 = 1_000;
+const VOTING_START: i64 = 2_000;
+const VOTING_END: i64 = 3_000;
+const CLAIM_DEADLINE: i64 = 4_000;
+
+const MATCHING_FUNDS: u64 = 10 * SOL;
+
+fn ix(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: solquad::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &solquad::ID).0
+}
+
+fn error(code: impl Into<u32>) -> ProgramError {
+    ProgramError::Custom(code.into())
+}
+
+// How a round is run, a lamport round of plain quadratic funding open to anyone by
+// default.
+#[derive(Clone, Copy)]
+struct Settings {
+    mint: Option<Pubkey>,
+    matching_mode: MatchingMode,
+    eligibility: Eligibility,
+    application_end: i64,
+    voting_start: i64,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            mint: None,
+            matching_mode: MatchingMode::Quadratic,
+            eligibility: Eligibility::Open,
+            application_end: APPLICATION_END,
+            voting_start: VOTING_START,
+        }
+    }
+}
+
+// A pool funded by its creator, in lamports or in tokens of the round's mint.
+struct Round {
+    creator: Pubkey,
+    round_id: u64,
+    escrow: Pubkey,
+    escrow_vault: Pubkey,
+    pool: Pubkey,
+    mint: Option<Pubkey>,
+}
+
+impl Round {
+    // Opens the first round of a new creator.
+    async fn open(bank: &mut Bank) -> Self {
+        Round::open_with(bank, Settings::default()).await
+    }
+
+    // Opens the first round of a new creator with the given settings.
+    async fn open_with(bank: &mut Bank, settings: Settings) -> Self {
+        let creator = bank.signer(100 * SOL);
+        init_registry(bank, creator).await;
+
+        Round::open_as(bank, creator, 0, settings).await.unwrap()
+    }
+
+    // Opens the creator's next round alongside this one.
+    async fn open_next(&self, bank: &mut Bank) -> Self {
+        let settings = Settings {
+            mint: self.mint,
+            ..Settings::default()
+        };
+        Round::open_as(bank, self.creator, self.round_id + 1, settings)
+            .await
+            .unwrap()
+    }
+
+    async fn open_as(
+        bank: &mut Bank,
+        creator: Pubkey,
+        round_id: u64,
+        settings: Settings,
+    ) -> std::result::Result<Self, ProgramError> {
+        let mint = settings.mint;
+        if let Some(mint) = mint {
+            bank.token_account(&mint, &creator, MATCHING_FUNDS);
+        }
+
+        let id = round_id.to_le_bytes();
+        let escrow = pda(&[b"escrow", creator.as_ref(), &id]);
+        let escrow_vault = pda(&[b"escrow-vault", escrow.as_ref()]);
+        let pool = pda(&[b"pool", creator.as_ref(), &id]);
+
+        bank.process(ix(
+            accounts::InitializeEscrow {
+                escrow_account: escrow,
+                escrow_vault,
+                sponsor_account: pda(&[b"sponsor", escrow.as_ref(), creator.as_ref()]),
+                escrow_signer: creator,
+                mint,
+                signer_token_account: token_account(mint, &creator),
+                token_program: mint.map(|_| token::ID),
+                system_program: system_program::ID,
+            },
+            instruction::InitializeEscrow {
+                round_id,
+                amount: MATCHING_FUNDS,
+            },
+        ))
+        .await?;
+
+        bank.process(ix(
+            accounts::InitializePool {
+                pool_account: pool,
+                escrow_account: escrow,
+                registry: registry(&creator),
+                pool_signer: creator,
+                vote_mint: mint,
+                system_program: system_program::ID,
+            },
+            instruction::InitializePool {
+                round_id,
+                application_end: settings.application_end,
+                voting_start: settings.voting_start,
+                voting_end: VOTING_END,
+                max_match_bps: None,
+                matching_mode: settings.matching_mode,
+                eligibility: settings.eligibility,
+                claim_deadline: CLAIM_DEADLINE,
+            },
+        ))
+        .await?;
+
+        Ok(Round {
+            creator,
+            round_id,
+            escrow,
+            escrow_vault,
+            pool,
+            mint,
+        })
+    }
+
+    // Creates a project owned and paid out to `owner` and adds it to the pool.
+    async fn add_project(&self, bank: &mut Bank) -> Entry {
+        let owner = bank.signer(10 * SOL);
+
+        let project = pda(&[b"project", self.pool.as_ref(), owner.as_ref()]);
+        let vault = pda(&[b"project-vault", project.as_ref()]);
+
+        bank.process(ix(
+            accounts::InitializeProject {
+                project_account: project,
+                project_vault: vault,
+                project_owner: owner,
+                pool_account: self.pool,
+                vote_mint: self.mint,
+                token_program: self.token_program(),
+                system_program: system_program::ID,
+            },
+            instruction::InitializeProject {
+                name: "project".to_string(),
+                description_uri: String::new(),
+                category: String::new(),
+                payout: owner,
+            },
+        ))
+        .await
+        .unwrap();
+
+        bank.process(self.add_ix(project)).await.unwrap();
+
+        Entry {
+            owner,
+            project,
+            vault,
+        }
+    }
+
+    fn add_ix(&self, project: Pubkey) -> Instruction {
+        ix(
+            accounts::AddProjectToPool {
+                escrow_account: self.escrow,
+                pool_account: self.pool,
+                project_account: project,
+                pool_creator: self.creator,
+                system_program: system_program::ID,
+            },
+            instruction::AddProjectToPool {},
+        )
+    }
+
+    fn vote_ix(&self, entry: &Entry, voter: Pubkey, amount: u64) -> Instruction {
+        self.gated_vote_ix(entry, voter, amount, vec![], None, None)
+    }
+
+    // Votes in a gated pool, proving eligibility with an allowlist proof or a holding.
+    fn gated_vote_ix(
+        &self,
+        entry: &Entry,
+        voter: Pubkey,
+        amount: u64,
+        allowlist_proof: Vec<[u8; 32]>,
+        gate_token_account: Option<Pubkey>,
+        gate_metadata: Option<Pubkey>,
+    ) -> Instruction {
+        ix(
+            accounts::VoteForProject {
+                pool_account: self.pool,
+                project_account: entry.project,
+                voter_account: self.voter_record(entry, &voter),
+                project_vault: entry.vault,
+                voter_sig: voter,
+                voter_token_account: token_account(self.mint, &voter),
+                gate_token_account,
+                gate_metadata,
+                token_program: self.token_program(),
+                system_program: system_program::ID,
+            },
+            instruction::VoteForProject {
+                amount,
+                allowlist_proof,
+            },
+        )
+    }
+
+    fn voter_record(&self, entry: &Entry, voter: &Pubkey) -> Pubkey {
+        pda(&[
+            b"voter",
+            self.pool.as_ref(),
+            entry.project.as_ref(),
+            voter.as_ref(),
+        ])
+    }
+
+    fn refund_ix(&self, entry: &Entry, voter: Pubkey) -> Instruction {
+        ix(
+            accounts::ClaimRefund {
+                pool_account: self.pool,
+                project_account: entry.project,
+                project_vault: entry.vault,
+                voter_account: self.voter_record(entry, &voter),
+                voter,
+                voter_token_account: token_account(self.mint, &voter),
+                token_program: self.token_program(),
+                system_program: system_program::ID,
+            },
+            instruction::ClaimRefund {},
+        )
+    }
+
+    fn update_ix(&self, entry: &Entry, name: &str, payout: Option<Pubkey>) -> Instruction {
+        ix(
+            accounts::UpdateProject {
+                pool_account: self.pool,
+                project_account: entry.project,
+                project_owner: entry.owner,
+            },
+            instruction::UpdateProject {
+                name: Some(name.to_string()),
+                description_uri: None,
+                category: None,
+                payout,
+            },
+        )
+    }
+
+    fn withdraw_ix(&self, entry: &Entry) -> Instruction {
+        ix(
+            accounts::WithdrawFromPool {
+                escrow_account: self.escrow,
+                pool_account: self.pool,
+                project_account: entry.project,
+                project_owner: entry.owner,
+            },
+            instruction::WithdrawFromPool {},
+        )
+    }
+
+    fn cancel_ix(&self) -> Instruction {
+        ix(
+            accounts::CancelRound {
+                pool_account: self.pool,
+                canceller: self.creator,
+            },
+            instruction::CancelRound {},
+        )
+    }
+
+    fn top_up_ix(&self, sponsor: Pubkey, amount: u64) -> Instruction {
+        ix(
+            accounts::TopUpEscrow {
+                escrow_account: self.escrow,
+                pool_account: self.pool,
+                escrow_vault: self.escrow_vault,
+                sponsor_account: pda(&[b"sponsor", self.escrow.as_ref(), sponsor.as_ref()]),
+                sponsor,
+                sponsor_token_account: token_account(self.mint, &sponsor),
+                token_program: self.token_program(),
+                system_program: system_program::ID,
+            },
+            instruction::TopUpEscrow { amount },
+        )
+    }
+
+    fn finalize_ix(&self) -> Instruction {
+        ix(
+            accounts::FinalizeRound {
+                pool_account: self.pool,
+            },
+            instruction::FinalizeRound {},
+        )
+    }
+
+    fn tally_ix(&self, escrow: Pubkey, projects: &[Pubkey]) -> Instruction {
+        self.tally_scored_ix(escrow, projects, vec![])
+    }
+
+    // Tallies `projects` with the scores a pairwise pool's creator submits for them.
+    fn tally_scored_ix(
+        &self,
+        escrow: Pubkey,
+        projects: &[Pubkey],
+        pairwise_scores: Vec<u128>,
+    ) -> Instruction {
+        let mut tally = ix(
+            accounts::TallyRound {
+                pool_account: self.pool,
+                escrow_account: escrow,
+                escrow_vault: pda(&[b"escrow-vault", escrow.as_ref()]),
+                cranker: self.creator,
+                system_program: system_program::ID,
+            },
+            instruction::TallyRound { pairwise_scores },
+        );
+        tally.accounts.extend(
+            projects
+                .iter()
+                .map(|project| AccountMeta::new(*project, false)),
+        );
+
+        tally
+    }
+
+    fn claim_match_ix(&self, entry: &Entry, payout: Pubkey) -> Instruction {
+        ix(
+            accounts::ClaimMatch {
+                escrow_account: self.escrow,
+                escrow_vault: self.escrow_vault,
+                pool_account: self.pool,
+                project_account: entry.project,
+                project_owner: entry.owner,
+                payout,
+                payout_token_account: token_account(self.mint, &payout),
+                token_program: self.token_program(),
+                system_program: system_program::ID,
+            },
+            instruction::ClaimMatch {},
+        )
+    }
+
+    fn sponsor_refund_ix(&self, sponsor: Pubkey) -> Instruction {
+        self.refund_sponsor_ix(sponsor, instruction::ClaimSponsorRefund {})
+    }
+
+    fn sweep_ix(&self, sponsor: Pubkey) -> Instruction {
+        self.refund_sponsor_ix(sponsor, instruction::SweepUnclaimed {})
+    }
+
+    // Pays `sponsor` their share of the escrow, through either refund instruction.
+    fn refund_sponsor_ix(&self, sponsor: Pubkey, data: impl InstructionData) -> Instruction {
+        ix(
+            accounts::RefundSponsor {
+                pool_account: self.pool,
+                escrow_account: self.escrow,
+                escrow_vault: self.escrow_vault,
+                sponsor_account: pda(&[b"sponsor", self.escrow.as_ref(), sponsor.as_ref()]),
+                sponsor,
+                sponsor_token_account: token_account(self.mint, &sponsor),
+                token_program: self.token_program(),
+                system_program: system_program::ID,
+            },
+            data,
+        )
+    }
+
+    fn token_program(&self) -> Option<Pubkey> {
+        self.mint.map(|_| token::ID)
+    }
+}
+
+// A project in a round.
+struct Entry {
+    owner: Pubkey,
+    project: Pubkey,
+    vault: Pubkey,
+}
+
+fn registry(creator: &Pubkey) -> Pubkey {
+    pda(&[b"registry", creator.as_ref()])
+}
+
+async fn init_registry(bank: &mut Bank, creator: Pubkey) {
+    bank.process(ix(
+        accounts::InitializeRegistry {
+            registry: registry(&creator),
+            creator,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeRegistry {},
+    ))
+    .await
+    .unwrap();
+}
+
+// The token account `owner` holds `mint` in, none for lamport rounds.
+fn token_account(mint: Option<Pubkey>, owner: &Pubkey) -> Option<Pubkey> {
+    mint.map(|mint| get_associated_token_address(owner, &mint))
+}
+
+fn voter(bank: &mut Bank) -> Pubkey {
+    bank.signer(10 * SOL)
+}
+
+// Creates the metadata of an NFT of `mint` claiming membership of `collection`, as the
+// metadata program would store it with empty strings and no other optional fields.
+fn nft_metadata(bank: &mut Bank, mint: &Pubkey, collection: &Pubkey, verified: bool) -> Pubkey {
+    let metadata = MetadataAccount::owner();
+    let key =
+        Pubkey::find_program_address(&[b"metadata", metadata.as_ref(), mint.as_ref()], &metadata).0;
+
+    let mut data = vec![mpl_token_metadata::types::Key::MetadataV1 as u8];
+    data.extend_from_slice(Pubkey::new_unique().as_ref());
+    data.extend_from_slice(mint.as_ref());
+    // name, symbol and uri
+    data.extend_from_slice(&[0; 4 * 3]);
+    data.extend_from_slice(&0u16.to_le_bytes());
+    // creators, primary sale, mutable, edition nonce and token standard
+    data.extend_from_slice(&[0, 0, 1, 0, 0]);
+    data.extend_from_slice(&[1, verified as u8]);
+    data.extend_from_slice(collection.as_ref());
+    // uses, collection details and programmable config
+    data.extend_from_slice(&[0, 0, 0]);
+    bank.set_account(&key, &metadata, data);
+
+    key
+}
+
+#[tokio::test]
+async fn repeat_votes_count_as_one_contribution() {
+    let mut bank = Bank::new(APPLICATIONS_OPEN).await;
+    let round = Round::open(&mut bank).await;
+    let entry = round.add_project(&mut bank).await;
+    let alice = voter(&mut bank);
+
+    bank.warp(VOTING_START).await;
+    bank.process(round.vote_ix(&entry, alice, 4 * SOL))
+        .await
+        .unwrap();
+    bank.process(round.vote_ix(&entry, alice, 5 * SOL))
+        .await
+        .unwrap();
+
+    // splitting a vote must not earn the voter a second square root
+    let project: Project = bank.account(&entry.project).await;
+    assert_eq!(
+        project.sum_sqrt_contributions,
+        qf::sqrt_contribution(9 * SOL)
+    );
+    assert_eq!(project.votes_count, 1);
+    assert_eq!(project.voter_amount, 9 * SOL);
+
+    let record: Voter = bank.account(&round.voter_record(&entry, &alice)).await;
+    assert_eq!(record.token_amount, 9 * SOL);
+
+    let votes = bank.events::<VoteCast>();
+    assert_eq!(votes.len(), 2);
+    assert_eq!(votes[1].amount, 5 * SOL);
+    assert_eq!(votes[1].contribution, 9 * SOL);
+}
+
+#[tokio::test]
+async fn votes_cannot_be_recorded_for_another_voter() {
+    let mut bank = Bank::new(APPLICATIONS_OPEN).await;
+    let round = Round::open(&mut bank).await;
+    let entry = round.add_project(&mut bank).await;
+    let (alice, mallory) = (voter(&mut bank), voter(&mut bank));
+
+    bank.warp(VOTING_START).await;
+    bank.process(round.vote_ix(&entry, alice, SOL))
+        .await
+        .unwrap();
+
+    let mut vote = round.vote_ix(&entry, mallory, SOL);
+    vote.accounts[2].pubkey = round.voter_record(&entry, &alice);
+
+    assert_eq!(
+        bank.process(vote).await,
+        Err(error(anchor_lang::error::ErrorCode::ConstraintSeeds))
+    );
+}
+
+#[tokio::test]
+async fn votes_are_only_accepted_while_voting_is_open() {
+    let mut bank = Bank::new(APPLICATIONS_OPEN).await;
+    let round = Round::open(&mut bank).await;
+    let entry = round.add_project(&mut bank).await;
+    let alice = voter(&mut bank);
+
+    // applications close before voting starts
+    bank.warp(APPLICATION_END).await;
+    assert_eq!(
+        bank.process(round.vote_ix(&entry, alice, SOL)).await,
+        Err(error(ErrorCode::NotInVotingPhase))
+    );
+
+    bank.warp(VOTING_START).await;
+    bank.process(round.vote_ix(&entry, alice, SOL))
+        .await
+        .unwrap();
+
+    bank.warp(VOTING_END).await;
+    assert_eq!(
+        bank.process(round.vote_ix(&entry, alice, SOL)).await,
+        Err(error(ErrorCode::NotInVotingPhase))
+    );
+}
+
+#[tokio::test]
+async fn projects_are_added_once_by_the_pool_creator() {
+    let mut bank = Bank::new(APPLICATIONS_OPEN).await;
+    let round = Round::open(&mut bank).await;
+    let entry = round.add_project(&mut bank).await;
+
+    assert_eq!(bank.events::<ProjectAdded>().len(), 1);
+    assert_eq!(
+        bank.process(round.add_ix(entry.project)).await,
+        Err(error(ErrorCode::ProjectExistsInPool))
+    );
+
+    // the project owner cannot add itself to somebody else's pool
+    let mut add = round.add_ix(entry.project);
+    add.accounts[3] = AccountMeta::new(entry.owner, true);
+    assert_eq!(
+        bank.process(add).await,
+        Err(error(anchor_lang::error::ErrorCode::ConstraintSeeds))
+    );
+
+    let pool: Pool = bank.account(&round.pool).await;
+    assert_eq!(pool.projects, vec![entry.owner]);
+    assert_eq!(pool.total_projects, 1);
+}
+
+#[tokio::test]
+async fn pool_cannot_be_backed_by_someone_elses_escrow() {
+    let mut bank = Bank::new(APPLICATIONS_OPEN).await;
+    let round = Round::open(&mut bank).await;
+
+    let mallory = bank.signer(10 * SOL);
+
+    init_registry(&mut bank, mallory).await;
+
+    let init = ix(
+        accounts::InitializePool {
+            pool_account: pda(&[b"pool", mallory.as_ref(), &0u64.to_le_bytes()]),
+            escrow_account: round.escrow,
+            registry: registry(&mallory),
+            pool_signer: mallory,
+            vote_mint: None,
+            system_program: system_program::ID,
+        },
+        instruction::InitializePool {
+            round_id: 0,
+            application_end: APPLICATION_END,
+            voting_start: VOTING_START,
+            voting_end: VOTING_END,
+            max_match_bps: None,
+            matching_mode: MatchingMode::Quadratic,
+            eligibility: Eligibility::Open,
+            claim_deadline: CLAIM_DEADLINE,
+        },
+    );
+
+    assert_eq!(
+        bank.process(init).await,
+        Err(error(anchor_lang::error::ErrorCode::ConstraintSeeds))
+    );
+}
+
+#[tokio::test]
+async fn matches_are_paid_from_the_pools_own_escrow() {
+    let mut bank = Bank::new(APPLICATIONS_OPEN).await;
+    let round = Round::open(&mut bank).await;
+    let other = Round::open(&mut bank).await;
+    let entry = round.add_project(&mut bank).await;
+    let alice = voter(&mut bank);
+
+    bank.warp(VOTING_START).await;
+    bank.process(round.vote_ix(&entry, alice, SOL))
+        .await
+        .unwrap();
+    bank.warp(VOTING_END).await;
+    bank.process(round.finalize_ix()).await.unwrap();
+
+    assert_eq!(
+        bank.process(round.tally_ix(other.escrow, &[entry.project]))
+            .await,
+        Err(error(ErrorCode::EscrowMismatch))
+    );
+
+    bank.process(round.tally_ix(round.escrow, &[entry.project]))
+        .await
+        .unwrap();
+
+    let mut claim = round.claim_match_ix(&entry, entry.owner);
+    claim.accounts[0].pubkey = other.escrow;
+    claim.accounts[1].pubkey = other.escrow_vault;
+    assert_eq!(
+        bank.process(claim).await,
+        Err(error(ErrorCode::EscrowMismatch))
+    );
+
+    assert_eq!(bank.lamports(&other.escrow_vault).await, {
+        let rent = Rent::default().minimum_balance(0);
+        MATCHING_FUNDS + rent
+    });
+}
+
+#[tokio::test]
+async fn tally_rejects_projects_of_another_pool() {
+    let mut bank = Bank::new(APPLICATIONS_OPEN).await;
+    let round = Round::open(&mut bank).await;
+    let other = Round::open(&mut bank).await;
+    let entry = round.add_project(&mut bank).await;
+    let stranger = other.add_project(&mut bank).await;
+    let alice = voter(&mut bank);
+
+    bank.warp(VOTING_START).await;
+    bank.process(round.vote_ix(&entry, alice, SOL))
+        .await
+        .unwrap();
+    bank.process(other.vote_ix(&stranger, alice, SOL))
+        .await
+        .unwrap();
+    bank.warp(VOTING_END).await;
+    bank.process(round.finalize_ix()).await.unwrap();
+
+    assert_eq!(
+        bank.process(round.tally_ix(round.escrow, &[stranger.project]))
+            .await,
+        Err(error(ErrorCode::ProjectNotInPool))
+    );
+
+    // nor can a project be counted twice to inflate the total score
+    assert_eq!(
+        bank.process(round.tally_ix(round.escrow, &[entry.project, entry.project]))
+            .await,
+        Err(error(ErrorCode::ProjectAlreadyTallied))
+    );
+}
+
+#[tokio::test]
+async fn matches_go_to_the_project_payout_once() {
+    let mut bank = Bank::new(APPLICATIONS_OPEN).await;
+    let round = Round::open(&mut bank).await;
+    let first = round.add_project(&mut bank).await;
+    let second = round.add_project(&mut bank).await;
+    let (alice, bob, carol) = (voter(&mut bank), voter(&mut bank), voter(&mut bank));
+
+    bank.warp(VOTING_START).await;
+    bank.process(round.vote_ix(&first, alice, SOL))
+        .await
+        .unwrap();
+    bank.process(round.vote_ix(&first, bob, SOL)).await.unwrap();
+    bank.process(round.vote_ix(&second, carol, 2 * SOL))
+        .await
+        .unwrap();
+
+    bank.warp(VOTING_END).await;
+    bank.process(round.finalize_ix()).await.unwrap();
+
+    // claims wait until every project of the pool is tallied
+    bank.process(round.tally_ix(round.escrow, &[first.project]))
+        .await
+        .unwrap();
+    assert_eq!(
+        bank.process(round.claim_match_ix(&first, first.owner))
+            .await,
+        Err(error(ErrorCode::TallyIncomplete))
+    );
+    bank.process(round.tally_ix(round.escrow, &[second.project]))
+        .await
+        .unwrap();
+
+    let tallied = bank.events::<ProjectTallied>();
+    assert_eq!(tallied.len(), 2);
+    assert!(tallied[0].score > tallied[1].score);
+
+    // a project cannot pay its match to anyone but its payout address
+    let mallory = voter(&mut bank);
+    assert_eq!(
+        bank.process(round.claim_match_ix(&first, mallory)).await,
+        Err(error(anchor_lang::error::ErrorCode::ConstraintAddress))
+    );
+
+    // nor claim another project's match
+    let mut claim = round.claim_match_ix(&second, first.owner);
+    claim.accounts[4] = AccountMeta::new_readonly(first.owner, true);
+    assert_eq!(
+        bank.process(claim).await,
+        Err(error(anchor_lang::error::ErrorCode::ConstraintSeeds))
+    );
+
+    let balance = bank.lamports(&first.owner).await;
+    bank.process(round.claim_match_ix(&first, first.owner))
+        .await
+        .unwrap();
+    let project: Project = bank.account(&first.project).await;
+    assert!(project.distributed_amt > 0);
+    assert_eq!(
+        bank.lamports(&first.owner).await,
+        balance + project.distributed_amt
+    );
+
+    assert_eq!(
+        bank.process(round.claim_match_ix(&first, first.owner))
+            .await,
+        Err(error(ErrorCode::MatchAlreadyPaid))
+    );
+
+    let claims = bank.events::<MatchClaimed>();
+    assert_eq!(claims.len(), 1);
+    assert_eq!(claims[0].project, first.project);
+    assert_eq!(claims[0].amount, project.distributed_amt);
+}
+
+#[tokio::test]
+async fn cancelled_round_refunds_voters_and_sponsors() {
+    let mut bank = Bank::new(APPLICATIONS_OPEN).await;
+    let round = Round::open(&mut bank).await;
+    let entry = round.add_project(&mut bank).await;
+    let alice = voter(&mut bank);
+
+    bank.warp(VOTING_START).await;
+    bank.process(round.vote_ix(&entry, alice, 3 * SOL))
+        .await
+        .unwrap();
+
+    let sponsor_record = pda(&[b"sponsor", round.escrow.as_ref(), round.creator.as_ref()]);
+    let refund_sponsor = round.sponsor_refund_ix(round.creator);
+    let refund_voter = round.refund_ix(&entry, alice);
+
+    assert_eq!(
+        bank.process(refund_voter.clone()).await,
+        Err(error(ErrorCode::RefundUnavailable))
+    );
+
+    let sponsor = voter(&mut bank);
+    bank.process(round.top_up_ix(sponsor, SOL)).await.unwrap();
+
+    bank.process(round.cancel_ix()).await.unwrap();
+
+    // sponsors cannot add to the escrow once refunds are owed out of it
+    assert_eq!(
+        bank.process(round.top_up_ix(sponsor, SOL)).await,
+        Err(error(ErrorCode::RoundCancelled))
+    );
+
+    // the project cannot walk away with the votes of a cancelled round
+    bank.warp(VOTING_END).await;
+    assert_eq!(
+        bank.process(round.finalize_ix()).await,
+        Err(error(ErrorCode::RoundCancelled))
+    );
+
+    let balance = bank.lamports(&alice).await;
+    bank.process(refund_voter).await.unwrap();
+    assert!(bank.lamports(&alice).await > balance + 3 * SOL);
+    assert!(!bank.exists(&round.voter_record(&entry, &alice)).await);
+
+    let balance = bank.lamports(&round.creator).await;
+    bank.process(refund_sponsor).await.unwrap();
+    assert!(bank.lamports(&round.creator).await > balance + MATCHING_FUNDS);
+    assert!(!bank.exists(&sponsor_record).await);
+
+    assert_eq!(bank.events::<RefundClaimed>()[0].amount, 3 * SOL);
+    assert_eq!(bank.events::<SponsorRefunded>()[0].amount, MATCHING_FUNDS);
+
+    // the escrow can only be topped up through the pool it funds
+    let mut top_up = round.top_up_ix(sponsor, SOL);
+    top_up.accounts[1].pubkey = Round::open(&mut bank).await.pool;
+    assert_eq!(
+        bank.process(top_up).await,
+        Err(error(ErrorCode::EscrowMismatch))
+    );
+}
+
+#[tokio::test]
+async fn creators_run_concurrent_rounds_with_their_own_escrows() {
+    let mut bank = Bank::new(APPLICATIONS_OPEN).await;
+    let first = Round::open(&mut bank).await;
+    let second = first.open_next(&mut bank).await;
+
+    let registry: RoundRegistry = bank.account(&registry(&first.creator)).await;
+    assert_eq!(registry.rounds, vec![first.pool, second.pool]);
+
+    let initialized = bank.events::<RegistryInitialized>();
+    assert_eq!(initialized.len(), 1);
+    assert_eq!(initialized[0].creator, first.creator);
+
+    let escrow: Escrow = bank.account(&second.escrow).await;
+    assert_eq!(escrow.pool, second.pool);
+    let pool: Pool = bank.account(&second.pool).await;
+    assert_eq!((pool.round_id, pool.escrow), (1, second.escrow));
+
+    // round ids cannot be skipped
+    assert_eq!(
+        Round::open_as(&mut bank, first.creator, 3, Settings::default())
+            .await
+            .err(),
+        Some(error(ErrorCode::InvalidRoundId))
+    );
+
+    // each round only pays matches out of its own escrow
+    let entry = first.add_project(&mut bank).await;
+    let (alice, bob) = (voter(&mut bank), voter(&mut bank));
+    bank.warp(VOTING_START).await;
+    bank.process(first.vote_ix(&entry, alice, SOL))
+        .await
+        .unwrap();
+    bank.process(first.vote_ix(&entry, bob, SOL)).await.unwrap();
+    bank.warp(VOTING_END).await;
+    bank.process(first.finalize_ix()).await.unwrap();
+
+    assert_eq!(
+        bank.process(first.tally_ix(second.escrow, &[entry.project]))
+            .await,
+        Err(error(ErrorCode::EscrowMismatch))
+    );
+    bank.process(first.tally_ix(first.escrow, &[entry.project]))
+        .await
+        .unwrap();
+    bank.process(first.claim_match_ix(&entry, entry.owner))
+        .await
+        .unwrap();
+
+    let rent = Rent::default().minimum_balance(0);
+    assert!(bank.lamports(&first.escrow_vault).await < MATCHING_FUNDS + rent);
+    assert_eq!(
+        bank.lamports(&second.escrow_vault).await,
+        MATCHING_FUNDS + rent
+    );
+}
+
+#[tokio::test]
+async fn token_rounds_move_funds_through_spl_token() {
+    let mut bank = Bank::new(APPLICATIONS_OPEN).await;
+    let mint = bank.mint();
+    let round = Round::open_with(
+        &mut bank,
+        Settings {
+            mint: Some(mint),
+            ..Settings::default()
+        },
+    )
+    .await;
+    let entry = round.add_project(&mut bank).await;
+    let (alice, bob) = (voter(&mut bank), voter(&mut bank));
+    let alice_tokens = bank.token_account(&mint, &alice, 4 * SOL);
+    bank.token_account(&mint, &bob, SOL);
+
+    bank.warp(VOTING_START).await;
+    assert_eq!(
+        bank.token_balance(&round.escrow_vault).await,
+        MATCHING_FUNDS
+    );
+
+    bank.process(round.vote_ix(&entry, alice, 4 * SOL))
+        .await
+        .unwrap();
+    bank.process(round.vote_ix(&entry, bob, SOL)).await.unwrap();
+    assert_eq!(bank.token_balance(&alice_tokens).await, 0);
+    assert_eq!(bank.token_balance(&entry.vault).await, 5 * SOL);
+
+    bank.warp(VOTING_END).await;
+    bank.process(round.finalize_ix()).await.unwrap();
+    bank.process(round.tally_ix(round.escrow, &[entry.project]))
+        .await
+        .unwrap();
+
+    let payout_tokens = bank.token_account(&mint, &entry.owner, 0);
+    bank.process(round.claim_match_ix(&entry, entry.owner))
+        .await
+        .unwrap();
+    let project: Project = bank.account(&entry.project).await;
+    assert!(project.distributed_amt > 0);
+    assert_eq!(
+        bank.token_balance(&payout_tokens).await,
+        project.distributed_amt
+    );
+    assert_eq!(
+        bank.token_balance(&round.escrow_vault).await,
+        MATCHING_FUNDS - project.distributed_amt
+    );
+
+    bank.process(ix(
+        accounts::ClaimContributions {
+            pool_account: round.pool,
+            project_account: entry.project,
+            project_vault: entry.vault,
+            project_owner: entry.owner,
+            payout: entry.owner,
+            payout_token_account: Some(payout_tokens),
+            token_program: Some(token::ID),
+            system_program: system_program::ID,
+        },
+        instruction::ClaimContributions {},
+    ))
+    .await
+    .unwrap();
+    assert_eq!(bank.token_balance(&entry.vault).await, 0);
+    assert_eq!(
+        bank.token_balance(&payout_tokens).await,
+        project.distributed_amt + 5 * SOL
+    );
+}
+
+#[tokio::test]
+async fn token_vaults_claim_addresses_already_holding_lamports() {
+    let mut bank = Bank::new(APPLICATIONS_OPEN).await;
+    let mint = bank.mint();
+    let creator = bank.signer(100 * SOL);
+    init_registry(&mut bank, creator).await;
+
+    // anyone can send lamports to the vault address before the escrow exists
+    let escrow = pda(&[b"escrow", creator.as_ref(), &0u64.to_le_bytes()]);
+    let escrow_vault = pda(&[b"escrow-vault", escrow.as_ref()]);
+    bank.fund(&escrow_vault, 1_000);
+
+    let round = Round::open_as(
+        &mut bank,
+        creator,
+        0,
+        Settings {
+            mint: Some(mint),
+            ..Settings::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(round.escrow_vault, escrow_vault);
+    assert_eq!(bank.token_balance(&escrow_vault).await, MATCHING_FUNDS);
+    assert_eq!(
+        bank.lamports(&escrow_vault).await,
+        Rent::default().minimum_balance(token::TokenAccount::LEN)
+    );
+}
+
+#[tokio::test]
+async fn quadratic_tally_scores_projects_on_chain() {
+    let mut bank = Bank::new(APPLICATIONS_OPEN).await;
+    let round = Round::open(&mut bank).await;
+    let entry = round.add_project(&mut bank).await;
+    let (alice, bob) = (voter(&mut bank), voter(&mut bank));
+
+    bank.warp(VOTING_START).await;
+    bank.process(round.vote_ix(&entry, alice, SOL))
+        .await
+        .unwrap();
+    bank.process(round.vote_ix(&entry, bob, 4 * SOL))
+        .await
+        .unwrap();
+    bank.warp(VOTING_END).await;
+    bank.process(round.finalize_ix()).await.unwrap();
+
+    // scores are only submitted for pairwise pools
+    assert_eq!(
+        bank.process(round.tally_scored_ix(round.escrow, &[entry.project], vec![1]))
+            .await,
+        Err(error(ErrorCode::InvalidPairwiseScores))
+    );
+
+    bank.process(round.tally_ix(round.escrow, &[entry.project]))
+        .await
+        .unwrap();
+
+    let project: Project = bank.account(&entry.project).await;
+    let score = qf::score(qf::sqrt_contribution(SOL) + qf::sqrt_contribution(4 * SOL))
+        .and_then(|score| qf::subsidy(score, 5 * SOL))
+        .unwrap();
+    assert!(score > 0);
+    assert_eq!(project.score, score);
+}
+
+#[tokio::test]
+async fn pairwise_scores_are_curated_by_the_creator_within_the_quadratic_bound() {
+    let mut bank = Bank::new(APPLICATIONS_OPEN).await;
+    let round = Round::open_with(
+        &mut bank,
+        Settings {
+            matching_mode: MatchingMode::Pairwise,
+            ..Settings::default()
+        },
+    )
+    .await;
+    let first = round.add_project(&mut bank).await;
+    let second = round.add_project(&mut bank).await;
+    let (alice, bob, carol) = (voter(&mut bank), voter(&mut bank), voter(&mut bank));
+
+    bank.warp(VOTING_START).await;
+
+    // alice and bob fund both projects together, carol only funds the second
+    let mut contributions = vec![];
+    for (entry, voter) in [
+        (&first, alice),
+        (&first, bob),
+        (&second, alice),
+        (&second, bob),
+        (&second, carol),
+    ] {
+        bank.process(round.vote_ix(entry, voter, SOL))
+            .await
+            .unwrap();
+        contributions.push(Contribution {
+            voter,
+            project: entry.project,
+            amount: SOL,
+        });
+    }
+    bank.warp(VOTING_END).await;
+    bank.process(round.finalize_ix()).await.unwrap();
+
+    let scores = pairwise::scores(&contributions, 1).unwrap();
+    let submitted = vec![scores[&first.project], scores[&second.project]];
+    let projects = [first.project, second.project];
+
+    // only the pool creator submits scores
+    let mut tally = round.tally_scored_ix(round.escrow, &projects, submitted.clone());
+    tally.accounts[3] = AccountMeta::new_readonly(alice, true);
+    assert_eq!(
+        bank.process(tally).await,
+        Err(error(ErrorCode::InvalidPairwiseScores))
+    );
+
+    // one for every project tallied
+    assert_eq!(
+        bank.process(round.tally_scored_ix(round.escrow, &projects, vec![submitted[0]]))
+            .await,
+        Err(error(ErrorCode::InvalidPairwiseScores))
+    );
+
+    // and never above a project's plain quadratic funding subsidy
+    assert_eq!(
+        bank.process(round.tally_scored_ix(round.escrow, &projects, vec![submitted[0], u128::MAX]))
+            .await,
+        Err(error(ErrorCode::InvalidPairwiseScores))
+    );
+
+    bank.process(round.tally_scored_ix(round.escrow, &projects, submitted.clone()))
+        .await
+        .unwrap();
+
+    let tallied = bank.events::<ProjectTallied>();
+    assert_eq!(
+        tallied.iter().map(|event| event.score).collect::<Vec<_>>(),
+        submitted
+    );
+
+    // the pair funding everything together is damped below plain quadratic funding
+    let project: Project = bank.account(&first.project).await;
+    let plain = qf::score(project.sum_sqrt_contributions)
+        .and_then(|score| qf::subsidy(score, project.voter_amount))
+        .unwrap();
+    assert!(project.score < plain);
+}
+
+#[tokio::test]
+async fn applications_close_before_voting_starts() {
+    let mut bank = Bank::new(APPLICATIONS_OPEN).await;
+    let creator = bank.signer(100 * SOL);
+    init_registry(&mut bank, creator).await;
+
+    let overlapping = Settings {
+        application_end: VOTING_START + 1,
+        ..Settings::default()
+    };
+    assert_eq!(
+        Round::open_as(&mut bank, creator, 0, overlapping)
+            .await
+            .err(),
+        Some(error(ErrorCode::InvalidRoundWindow))
+    );
+
+    // voting may start the moment applications close
+    let back_to_back = Settings {
+        application_end: VOTING_START,
+        ..Settings::default()
+    };
+    let round = Round::open_with(&mut bank, back_to_back).await;
+    let pool: Pool = bank.account(&round.pool).await;
+    assert_eq!(pool.application_end, pool.voting_start);
+}
+
+#[tokio::test]
+async fn projects_are_updated_by_their_owner_until_voting_starts() {
+    let mut bank = Bank::new(APPLICATIONS_OPEN).await;
+    let round = Round::open(&mut bank).await;
+    let entry = round.add_project(&mut bank).await;
+    let cancelled = Round::open(&mut bank).await;
+    let stopped = cancelled.add_project(&mut bank).await;
+    let payout = Pubkey::new_unique();
+
+    bank.process(round.update_ix(&entry, "renamed", Some(payout)))
+        .await
+        .unwrap();
+
+    let project: Project = bank.account(&entry.project).await;
+    assert_eq!(project.project_name, "renamed");
+    assert_eq!(project.payout, payout);
+    let updated = bank.events::<ProjectUpdated>();
+    assert_eq!(updated.len(), 1);
+    assert_eq!(updated[0].project, entry.project);
+
+    // nobody else can edit the project
+    let mallory = voter(&mut bank);
+    let mut update = round.update_ix(&entry, "mallory", Some(mallory));
+    update.accounts[2] = AccountMeta::new_readonly(mallory, true);
+    assert_eq!(
+        bank.process(update).await,
+        Err(error(anchor_lang::error::ErrorCode::ConstraintSeeds))
+    );
+
+    // nor can a project be edited once its round is cancelled
+    bank.process(cancelled.cancel_ix()).await.unwrap();
+    assert_eq!(
+        bank.process(cancelled.update_ix(&stopped, "cancelled", None))
+            .await,
+        Err(error(ErrorCode::RoundCancelled))
+    );
+
+    // or once voting has started
+    bank.warp(VOTING_START).await;
+    assert_eq!(
+        bank.process(round.update_ix(&entry, "late", None)).await,
+        Err(error(ErrorCode::VotingStarted))
+    );
+}
+
+#[tokio::test]
+async fn updates_are_rejected_once_the_round_is_finalized() {
+    let mut bank = Bank::new(APPLICATIONS_OPEN).await;
+    let round = Round::open(&mut bank).await;
+    let entry = round.add_project(&mut bank).await;
+
+    bank.warp(VOTING_END).await;
+    bank.process(round.finalize_ix()).await.unwrap();
+
+    assert_eq!(
+        bank.process(round.update_ix(&entry, "finalized", None))
+            .await,
+        Err(error(ErrorCode::RoundFinalized))
+    );
+}
+
+#[tokio::test]
+async fn withdrawn_projects_leave_the_pool_and_refund_their_voters() {
+    let mut bank = Bank::new(APPLICATIONS_OPEN).await;
+    let round = Round::open(&mut bank).await;
+    let leaving = round.add_project(&mut bank).await;
+    let staying = round.add_project(&mut bank).await;
+    let (alice, bob) = (voter(&mut bank), voter(&mut bank));
+
+    bank.warp(VOTING_START).await;
+    bank.process(round.vote_ix(&leaving, alice, 2 * SOL))
+        .await
+        .unwrap();
+    bank.process(round.vote_ix(&leaving, bob, SOL))
+        .await
+        .unwrap();
+    bank.process(round.vote_ix(&staying, bob, SOL))
+        .await
+        .unwrap();
+
+    // voters cannot pull their votes while the project is still in the pool
+    assert_eq!(
+        bank.process(round.refund_ix(&leaving, alice)).await,
+        Err(error(ErrorCode::RefundUnavailable))
+    );
+
+    bank.process(round.withdraw_ix(&leaving)).await.unwrap();
+    assert_eq!(
+        bank.process(round.withdraw_ix(&leaving)).await,
+        Err(error(ErrorCode::ProjectNotInPool))
+    );
+
+    let pool: Pool = bank.account(&round.pool).await;
+    assert_eq!(pool.projects, vec![staying.owner]);
+    assert_eq!(pool.total_projects, 1);
+    let escrow: Escrow = bank.account(&round.escrow).await;
+    assert!(!escrow.project_reciever_addresses.contains(&leaving.owner));
+    let withdrawn = bank.events::<ProjectWithdrawn>();
+    assert_eq!(withdrawn.len(), 1);
+    assert_eq!(withdrawn[0].project, leaving.project);
+
+    // every voter gets their contribution back, along with their record's rent
+    let record_rent = bank.lamports(&round.voter_record(&leaving, &alice)).await;
+    let balance = bank.lamports(&alice).await;
+    bank.process(round.refund_ix(&leaving, alice))
+        .await
+        .unwrap();
+    assert_eq!(bank.lamports(&alice).await, balance + 2 * SOL + record_rent);
+    assert!(!bank.exists(&round.voter_record(&leaving, &alice)).await);
+
+    bank.process(round.refund_ix(&leaving, bob)).await.unwrap();
+    let project: Project = bank.account(&leaving.project).await;
+    assert_eq!(
+        (
+            project.voter_amount,
+            project.votes_count,
+            project.sum_sqrt_contributions
+        ),
+        (0, 0, 0)
+    );
+    assert_eq!(
+        bank.lamports(&leaving.vault).await,
+        Rent::default().minimum_balance(0)
+    );
+
+    let refunds = bank.events::<RefundClaimed>();
+    assert_eq!(
+        refunds
+            .iter()
+            .map(|refund| refund.amount)
+            .collect::<Vec<_>>(),
+        vec![2 * SOL, SOL]
+    );
+
+    // the round goes on with the projects left in it
+    bank.warp(VOTING_END).await;
+    bank.process(round.finalize_ix()).await.unwrap();
+    bank.process(round.tally_ix(round.escrow, &[staying.project]))
+        .await
+        .unwrap();
+    let pool: Pool = bank.account(&round.pool).await;
+    assert!(pool.is_tallied());
+}
+
+#[tokio::test]
+async fn unclaimed_matches_are_swept_back_to_sponsors_after_the_deadline() {
+    let mut bank = Bank::new(APPLICATIONS_OPEN).await;
+    let round = Round::open(&mut bank).await;
+    let first = round.add_project(&mut bank).await;
+    let second = round.add_project(&mut bank).await;
+    let (alice, bob) = (voter(&mut bank), voter(&mut bank));
+    let (sponsor, patron) = (voter(&mut bank), voter(&mut bank));
+
+    bank.process(round.top_up_ix(sponsor, SOL)).await.unwrap();
+    bank.process(round.top_up_ix(patron, 2 * SOL))
+        .await
+        .unwrap();
+
+    bank.warp(VOTING_START).await;
+    bank.process(round.vote_ix(&first, alice, SOL))
+        .await
+        .unwrap();
+    bank.process(round.vote_ix(&second, bob, 2 * SOL))
+        .await
+        .unwrap();
+
+    bank.warp(VOTING_END).await;
+    bank.process(round.finalize_ix()).await.unwrap();
+    bank.process(round.tally_ix(round.escrow, &[first.project, second.project]))
+        .await
+        .unwrap();
+    bank.process(round.claim_match_ix(&first, first.owner))
+        .await
+        .unwrap();
+
+    // projects have until the deadline to claim their match
+    assert_eq!(
+        bank.process(round.sweep_ix(sponsor)).await,
+        Err(error(ErrorCode::ClaimWindowOpen))
+    );
+
+    bank.warp(CLAIM_DEADLINE).await;
+    assert_eq!(
+        bank.process(round.claim_match_ix(&second, second.owner))
+            .await,
+        Err(error(ErrorCode::ClaimWindowClosed))
+    );
+
+    // nor can a tallied round be cancelled to get at the votes
+    let mut cancel = round.cancel_ix();
+    cancel.accounts[1] = AccountMeta::new_readonly(alice, true);
+    assert_eq!(
+        bank.process(cancel).await,
+        Err(error(ErrorCode::RoundTallied))
+    );
+
+    let leftover = bank.lamports(&round.escrow_vault).await - Rent::default().minimum_balance(0);
+    let unclaimed: Project = bank.account(&second.project).await;
+    assert!(leftover >= unclaimed.distributed_amt);
+
+    let deposits = MATCHING_FUNDS + 3 * SOL;
+    let mut swept = 0;
+    for (sponsor, deposit) in [(sponsor, SOL), (round.creator, MATCHING_FUNDS)] {
+        let balance = bank.lamports(&sponsor).await;
+        bank.process(round.sweep_ix(sponsor)).await.unwrap();
+        let share =
+            qf::mul_div(leftover as u128, deposit as u128, deposits as u128).unwrap() as u64;
+        assert_eq!(bank.events::<SponsorRefunded>()[0].amount, share);
+        assert!(bank.lamports(&sponsor).await > balance + share);
+        swept += share;
+    }
+    assert_eq!(
+        bank.process(round.sweep_ix(sponsor)).await,
+        Err(error(anchor_lang::error::ErrorCode::AccountNotInitialized))
+    );
+
+    // the last sponsor is paid the rounding left over by the others
+    bank.process(round.sweep_ix(patron)).await.unwrap();
+    assert_eq!(swept + bank.events::<SponsorRefunded>()[0].amount, leftover);
+    assert_eq!(
+        bank.lamports(&round.escrow_vault).await,
+        Rent::default().minimum_balance(0)
+    );
+
+    let pool: Pool = bank.account(&round.pool).await;
+    assert_eq!(pool.refund_pool, Some(leftover));
+    assert_eq!(pool.refunded_deposits, deposits);
+}
+
+#[tokio::test]
+async fn abandoned_rounds_can_be_cancelled_by_anyone_after_the_deadline() {
+    let mut bank = Bank::new(APPLICATIONS_OPEN).await;
+    let round = Round::open(&mut bank).await;
+    let entry = round.add_project(&mut bank).await;
+    let alice = voter(&mut bank);
+
+    bank.warp(VOTING_START).await;
+    bank.process(round.vote_ix(&entry, alice, 3 * SOL))
+        .await
+        .unwrap();
+
+    // the creator never finalizes, until the deadline only they can call the round off
+    let mut cancel = round.cancel_ix();
+    cancel.accounts[1] = AccountMeta::new_readonly(alice, true);
+    bank.warp(VOTING_END).await;
+    assert_eq!(
+        bank.process(cancel.clone()).await,
+        Err(error(ErrorCode::CancelUnauthorized))
+    );
+
+    bank.warp(CLAIM_DEADLINE).await;
+    bank.process(cancel).await.unwrap();
+    assert!(bank.account::<Pool>(&round.pool).await.cancelled);
+
+    let balance = bank.lamports(&alice).await;
+    bank.process(round.refund_ix(&entry, alice)).await.unwrap();
+    assert!(bank.lamports(&alice).await > balance + 3 * SOL);
+
+    bank.process(round.sponsor_refund_ix(round.creator))
+        .await
+        .unwrap();
+    assert_eq!(bank.events::<SponsorRefunded>()[0].amount, MATCHING_FUNDS);
+}
+
+#[tokio::test]
+async fn allowlisted_pools_only_accept_proven_voters() {
+    let mut bank = Bank::new(APPLICATIONS_OPEN).await;
+    let (alice, bob, mallory) = (voter(&mut bank), voter(&mut bank), voter(&mut bank));
+    let (alice_leaf, bob_leaf) = (merkle::leaf(&alice), merkle::leaf(&bob));
+    let round = Round::open_with(
+        &mut bank,
+        Settings {
+            eligibility: Eligibility::Allowlist {
+                root: merkle::node(&alice_leaf, &bob_leaf),
+            },
+            ..Settings::default()
+        },
+    )
+    .await;
+    let entry = round.add_project(&mut bank).await;
+
+    bank.warp(VOTING_START).await;
+    bank.process(round.gated_vote_ix(&entry, alice, SOL, vec![bob_leaf], None, None))
+        .await
+        .unwrap();
+
+    // voters off the list, and proofs of another voter's leaf, are turned away
+    for (voter, proof) in [
+        (mallory, vec![bob_leaf]),
+        (bob, vec![bob_leaf]),
+        (bob, vec![]),
+    ] {
+        assert_eq!(
+            bank.process(round.gated_vote_ix(&entry, voter, SOL, proof, None, None))
+                .await,
+            Err(error(ErrorCode::VoterNotEligible))
+        );
+    }
+
+    bank.process(round.gated_vote_ix(&entry, bob, SOL, vec![alice_leaf], None, None))
+        .await
+        .unwrap();
+    assert_eq!(bank.events::<VoteCast>().len(), 2);
+}
+
+#[tokio::test]
+async fn token_gated_pools_check_the_voters_own_holding() {
+    let mut bank = Bank::new(APPLICATIONS_OPEN).await;
+    let gate = bank.mint();
+    let round = Round::open_with(
+        &mut bank,
+        Settings {
+            eligibility: Eligibility::TokenGate {
+                mint: gate,
+                min_balance: 5,
+            },
+            ..Settings::default()
+        },
+    )
+    .await;
+    let entry = round.add_project(&mut bank).await;
+    let (alice, bob) = (voter(&mut bank), voter(&mut bank));
+    let alice_holding = bank.token_account(&gate, &alice, 5);
+    let bob_holding = bank.token_account(&gate, &bob, 4);
+    let other_mint = bank.mint();
+    let bob_other_holding = bank.token_account(&other_mint, &bob, 5);
+
+    bank.warp(VOTING_START).await;
+    bank.process(round.gated_vote_ix(&entry, alice, SOL, vec![], Some(alice_holding), None))
+        .await
+        .unwrap();
+
+    // too small a holding, a holding of another mint, someone else's holding or none at all
+    for holding in [
+        Some(bob_holding),
+        Some(bob_other_holding),
+        Some(alice_holding),
+        None,
+    ] {
+        assert_eq!(
+            bank.process(round.gated_vote_ix(&entry, bob, SOL, vec![], holding, None))
+                .await,
+            Err(error(ErrorCode::VoterNotEligible))
+        );
+    }
+}
+
+#[tokio::test]
+async fn collection_gated_pools_require_a_verified_nft_held_by_the_voter() {
+    let mut bank = Bank::new(APPLICATIONS_OPEN).await;
+    let collection = Pubkey::new_unique();
+    let round = Round::open_with(
+        &mut bank,
+        Settings {
+            eligibility: Eligibility::Collection { collection },
+            ..Settings::default()
+        },
+    )
+    .await;
+    let entry = round.add_project(&mut bank).await;
+    let (alice, bob, carol) = (voter(&mut bank), voter(&mut bank), voter(&mut bank));
+
+    let nft = |bank: &mut Bank, owner: &Pubkey, collection: &Pubkey, verified: bool| {
+        let mint = bank.mint();
+        let holding = bank.token_account(&mint, owner, 1);
+        (holding, nft_metadata(bank, &mint, collection, verified))
+    };
+    let (alice_nft, alice_metadata) = nft(&mut bank, &alice, &collection, true);
+    let (bob_nft, bob_metadata) = nft(&mut bank, &bob, &collection, false);
+    let (carol_nft, carol_metadata) = nft(&mut bank, &carol, &Pubkey::new_unique(), true);
+
+    bank.warp(VOTING_START).await;
+    let vote = |voter: Pubkey, nft: Pubkey, metadata: Pubkey| {
+        round.gated_vote_ix(&entry, voter, SOL, vec![], Some(nft), Some(metadata))
+    };
+    bank.process(vote(alice, alice_nft, alice_metadata))
+        .await
+        .unwrap();
+
+    // an unverified member, a verified member of another collection, someone else's NFT
+    // and metadata of another mint than the one held
+    for instruction in [
+        vote(bob, bob_nft, bob_metadata),
+        vote(carol, carol_nft, carol_metadata),
+        vote(bob, alice_nft, alice_metadata),
+        vote(bob, bob_nft, alice_metadata),
+    ] {
+        assert_eq!(
+            bank.process(instruction).await,
+            Err(error(ErrorCode::VoterNotEligible))
+        );
+    }
+}
@@ -3,10 +3,11 @@ require("dotenv").config();
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import idl from "../target/idl/solquad.json";
-import { Solquad } from "../target/idl/solquad";
+import { Solquad } from "../target/types/solquad";
 
 import { utf8 } from "@coral-xyz/anchor/dist/cjs/utils/bytes";
 import { BN } from "bn.js";
+import { assert } from "chai";
 
 describe("solquad", async () => {
   const connection = new anchor.web3.Connection(
@@ -42,13 +43,32 @@ describe("solquad", async () => {
   const voter5 = anchor.web3.Keypair.generate();
   const voter6 = anchor.web3.Keypair.generate();
 
+  // every creator opens their first round
+  const roundId = new BN(0);
+  const roundIdSeed = roundId.toArrayLike(Buffer, "le", 8);
+
+  const [registryPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode("registry"), admin.publicKey.toBuffer()],
+    program.programId
+  );
+
   const [escrowPDA] = await anchor.web3.PublicKey.findProgramAddressSync(
-    [utf8.encode("escrow"), admin.publicKey.toBuffer()],
+    [utf8.encode("escrow"), admin.publicKey.toBuffer(), roundIdSeed],
+    program.programId
+  );
+
+  const [escrowVaultPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode("escrow-vault"), escrowPDA.toBytes()],
+    program.programId
+  );
+
+  const [sponsorPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode("sponsor"), escrowPDA.toBytes(), admin.publicKey.toBuffer()],
     program.programId
   );
 
   const [poolPDA] = anchor.web3.PublicKey.findProgramAddressSync(
-    [utf8.encode("pool"), admin.publicKey.toBuffer()],
+    [utf8.encode("pool"), admin.publicKey.toBuffer(), roundIdSeed],
     program.programId
   );
 
@@ -57,35 +77,107 @@ describe("solquad", async () => {
     program.programId
   );
 
+  const [projectVaultPDA1] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode("project-vault"), projectPDA1.toBytes()],
+    program.programId
+  );
+
+  const [voterPDA1] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      utf8.encode("voter"),
+      poolPDA.toBytes(),
+      projectPDA1.toBytes(),
+      admin.publicKey.toBuffer(),
+    ],
+    program.programId
+  );
+
+  const [differentRegistryPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+    [utf8.encode("registry"), admin2.publicKey.toBuffer()],
+    program.programId
+  );
+
   const [differentEscrowPDA] = anchor.web3.PublicKey.findProgramAddressSync(
-    [utf8.encode("escrow"), admin2.publicKey.toBuffer()],
+    [utf8.encode("escrow"), admin2.publicKey.toBuffer(), roundIdSeed],
     program.programId
   );
 
   const [differentPoolPDA] = anchor.web3.PublicKey.findProgramAddressSync(
-    [utf8.encode("pool"), admin2.publicKey.toBuffer()],
+    [utf8.encode("pool"), admin2.publicKey.toBuffer(), roundIdSeed],
+    program.programId
+  );
+
+  const [differentEscrowVaultPDA] =
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [utf8.encode("escrow-vault"), differentEscrowPDA.toBytes()],
+      program.programId
+    );
+
+  const [differentSponsorPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      utf8.encode("sponsor"),
+      differentEscrowPDA.toBytes(),
+      admin2.publicKey.toBuffer(),
+    ],
     program.programId
   );
 
+  // the round is timed against the validator's clock, which drifts from the host's.
+  // Applications stay open long enough for tests 1-3, voting for test 4 to vote once.
+  const PHASE_SECONDS = 30;
+  let applicationEnd: BN;
+  let votingStart: BN;
+  let votingEnd: BN;
+  let claimDeadline: BN;
+
   before(async () => {
     await airdrop(admin, provider);
     await airdrop(admin2, provider);
+
+    const roundStart = await clockTime(connection);
+    applicationEnd = new BN(roundStart + PHASE_SECONDS);
+    votingStart = applicationEnd.clone();
+    votingEnd = votingStart.addn(PHASE_SECONDS);
+    claimDeadline = votingEnd.addn(3600);
   });
 
   // Test 1
   it("initializes escrow and pool", async () => {
     const poolIx = await program.methods
-      .initializePool()
+      .initializePool(
+        roundId,
+        applicationEnd,
+        votingStart,
+        votingEnd,
+        null,
+        { quadratic: {} },
+        { open: {} },
+        claimDeadline
+      )
       .accounts({
         poolAccount: poolPDA,
+        escrowAccount: escrowPDA,
+        registry: registryPDA,
+        voteMint: null,
       })
       .instruction();
 
+    const registryIx = await program.methods
+      .initializeRegistry()
+      .accounts({ registry: registryPDA })
+      .instruction();
+
     const escrowAndPoolTx = await program.methods
-      .initializeEscrow(new BN(10000))
+      .initializeEscrow(roundId, new BN(10000))
       .accounts({
         escrowAccount: escrowPDA,
+        escrowVault: escrowVaultPDA,
+        sponsorAccount: sponsorPDA,
+        mint: null,
+        signerTokenAccount: null,
+        tokenProgram: null,
       })
+      .preInstructions([registryIx])
       .postInstructions([poolIx])
       .rpc();
 
@@ -93,7 +185,7 @@ describe("solquad", async () => {
   });
 
   // Test 2
-  it("creates project and add it to the pool twice", async () => {
+  it("creates project and refuses to add it to the pool twice", async () => {
     const addProjectIx = await program.methods
       .addProjectToPool()
       .accounts({
@@ -104,19 +196,42 @@ describe("solquad", async () => {
       .instruction();
 
     const addProjectTx = await program.methods
-      .initializeProject("My Project")
+      .initializeProject(
+        "My Project",
+        "https://example.com/my-project.json",
+        "public-goods",
+        admin.publicKey
+      )
       .accounts({
         projectAccount: projectPDA1,
+        projectVault: projectVaultPDA1,
         poolAccount: poolPDA,
+        voteMint: null,
+        tokenProgram: null,
       })
-      .postInstructions([addProjectIx, addProjectIx])
+      .postInstructions([addProjectIx])
       .rpc();
 
     console.log(
-      "Project successfully created and added to the pool twice",
+      "Project successfully created and added to the pool",
       addProjectTx
     );
 
+    let rejected = false;
+    try {
+      await program.methods
+        .addProjectToPool()
+        .accounts({
+          escrowAccount: escrowPDA,
+          poolAccount: poolPDA,
+          projectAccount: projectPDA1,
+        })
+        .rpc();
+    } catch (err) {
+      rejected = true;
+    }
+    assert(rejected, "the project was added to the pool twice");
+
     const data = await program.account.pool.fetch(poolPDA);
     console.log("data projects", data.projects);
   });
@@ -124,33 +239,57 @@ describe("solquad", async () => {
   // Test 3
   it("tries to add the project in the different pool", async () => {
     const poolIx = await program2.methods
-      .initializePool()
+      .initializePool(
+        roundId,
+        applicationEnd,
+        votingStart,
+        votingEnd,
+        null,
+        { quadratic: {} },
+        { open: {} },
+        claimDeadline
+      )
       .accounts({
         poolAccount: differentPoolPDA,
+        escrowAccount: differentEscrowPDA,
+        registry: differentRegistryPDA,
+        voteMint: null,
       })
       .instruction();
 
-    const escrowIx = await program2.methods
-      .initializeEscrow(new BN(10000))
-      .accounts({
-        escrowAccount: differentEscrowPDA,
-      })
+    const registryIx = await program2.methods
+      .initializeRegistry()
+      .accounts({ registry: differentRegistryPDA })
       .instruction();
 
-    const addProjectTx = await program2.methods
-      .addProjectToPool()
+    await program2.methods
+      .initializeEscrow(roundId, new BN(10000))
       .accounts({
-        projectAccount: projectPDA1,
-        poolAccount: differentPoolPDA,
         escrowAccount: differentEscrowPDA,
+        escrowVault: differentEscrowVaultPDA,
+        sponsorAccount: differentSponsorPDA,
+        mint: null,
+        signerTokenAccount: null,
+        tokenProgram: null,
       })
-      .preInstructions([escrowIx, poolIx])
+      .preInstructions([registryIx])
+      .postInstructions([poolIx])
       .rpc();
 
-    console.log(
-      "Different pool is created and the project is inserted into it",
-      addProjectTx
-    );
+    let rejected = false;
+    try {
+      await program2.methods
+        .addProjectToPool()
+        .accounts({
+          projectAccount: projectPDA1,
+          poolAccount: differentPoolPDA,
+          escrowAccount: differentEscrowPDA,
+        })
+        .rpc();
+    } catch (err) {
+      rejected = true;
+    }
+    assert(rejected, "the project was added to a pool it wasn't created for");
 
     const data = await program.account.pool.fetch(differentPoolPDA);
     console.log("data projects", data.projects);
@@ -158,24 +297,65 @@ describe("solquad", async () => {
 
   // Test 4
   it("votes for the project and distributes the rewards", async () => {
-    const distribIx = await program.methods
-      .distributeEscrowAmount()
+    const tallyIx = await program.methods
+      .tallyRound([])
+      .accounts({
+        poolAccount: poolPDA,
+        escrowAccount: escrowPDA,
+        escrowVault: escrowVaultPDA,
+        cranker: admin.publicKey,
+      })
+      .remainingAccounts([
+        { pubkey: projectPDA1, isWritable: true, isSigner: false },
+      ])
+      .instruction();
+
+    const claimIx = await program.methods
+      .claimMatch()
       .accounts({
         escrowAccount: escrowPDA,
+        escrowVault: escrowVaultPDA,
         poolAccount: poolPDA,
         projectAccount: projectPDA1,
+        projectOwner: admin.publicKey,
+        payout: admin.publicKey,
+        payoutTokenAccount: null,
+        tokenProgram: null,
       })
       .instruction();
 
+    const finalizeIx = await program.methods
+      .finalizeRound()
+      .accounts({
+        poolAccount: poolPDA,
+      })
+      .instruction();
+
+    await waitForClock(connection, votingStart.toNumber());
+
     const voteTx = await program.methods
-      .voteForProject(new BN(10))
+      .voteForProject(new BN(10), [])
       .accounts({
         poolAccount: poolPDA,
         projectAccount: projectPDA1,
+        voterAccount: voterPDA1,
+        projectVault: projectVaultPDA1,
+        voterTokenAccount: null,
+        gateTokenAccount: null,
+        gateMetadata: null,
+        tokenProgram: null,
       })
-      .postInstructions([distribIx])
       .rpc();
 
+    await waitForClock(connection, votingEnd.toNumber());
+
+    const distribTx = new anchor.web3.Transaction().add(
+      finalizeIx,
+      tallyIx,
+      claimIx
+    );
+    await provider.sendAndConfirm(distribTx);
+
     console.log(
       "Successfully voted on the project and distributed weighted rewards",
       voteTx
@@ -186,6 +366,29 @@ describe("solquad", async () => {
   });
 });
 
+function sleep(ms: number) {
+  return new Promise((resolve) => setTimeout(resolve, Math.max(ms, 0)));
+}
+
+// Unix timestamp of the validator's Clock sysvar.
+async function clockTime(connection: anchor.web3.Connection): Promise<number> {
+  const clock = await connection.getAccountInfo(
+    anchor.web3.SYSVAR_CLOCK_PUBKEY
+  );
+  // slot, epoch start timestamp, epoch and leader schedule epoch come first
+  return new BN(clock.data.subarray(32, 40), "le").toNumber();
+}
+
+// Waits until the validator's clock reaches `timestamp`.
+async function waitForClock(
+  connection: anchor.web3.Connection,
+  timestamp: number
+) {
+  while ((await clockTime(connection)) < timestamp) {
+    await sleep(500);
+  }
+}
+
 async function airdrop(user, provider) {
   const AIRDROP_AMOUNT = 5 * anchor.web3.LAMPORTS_PER_SOL; // 5 SOL
 